@@ -1,23 +1,42 @@
 use iced::widget::{button, column, container, row, text};
-use iced::{time, Border, Color, Element, Length, Shadow, Subscription, Task, Theme};
+use iced::{event, system, theme, time, window, Border, Color, Element, Event, Length, Point, Shadow, Size, Subscription, Task, Theme};
 
+use monitor_app::{
+    clamp_to_monitors, cpu_usage, create_metric_row, create_themed_tab_button, format_duration,
+    format_interval, level_icon_rgba, memory_available, memory_pressure, memory_usage, monitor_at, monitors,
+    smoothed_latest, snap_to_edges, swap_usage, system_refresh_kind, threshold_color, top_right,
+    tray_color_value, uptime_seconds,
+    Config, History, Icon, LoadLevel, Snapshot, TabSize,
+};
+#[cfg(feature = "temperature")]
+use monitor_app::{cpu_temperature, hottest_core, per_core_temperatures};
+#[cfg(feature = "alerts")]
+use monitor_app::notify_cpu;
+#[cfg(feature = "gpu")]
+use monitor_app::gpu_usage;
 #[cfg(feature = "battery")]
-use monitor_app::get_battery_info;
+use monitor_app::{format_eta, get_battery_info_detailed};
 #[cfg(feature = "disk")]
-use monitor_app::get_disk_usage;
+use monitor_app::{format_gb, per_disk_usage, scoped_disk_usage, DiskScope, DiskUsage};
 #[cfg(feature = "network")]
-use monitor_app::network_deltas;
+use monitor_app::{
+    format_bytes, is_excluded_interface, network_total_bytes, NetDisplay, RateTracker,
+};
+#[cfg(feature = "processes")]
+use monitor_app::{process_counts, refresh_processes, top_processes};
 
 #[cfg(feature = "disk")]
 use sysinfo::Disks;
 #[cfg(feature = "network")]
 use sysinfo::Networks;
 use sysinfo::System;
+#[cfg(feature = "temperature")]
+use sysinfo::Components;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tray_icon::{
-    menu::{Menu, MenuItem},
-    TrayIconBuilder,
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    TrayIcon, TrayIconBuilder, TrayIconEvent,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -25,61 +44,191 @@ enum Tab {
     System,
     Network,
     Power,
+    #[cfg(feature = "processes")]
+    Processes,
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     Tick,
     TabSelected(Tab),
+    WindowMoved(Point),
+    DragWindow,
+    PollTrayMenu,
+    // Curseur (coordonnées logiques) lors du dernier clic sur l'icône, puis taille
+    // du moniteur qui le contient
+    ShowOnCursorDisplay(window::Id, Point),
+    SystemThemeChanged(theme::Mode),
+    ToggleTiny,
+    ToggleTheme,
+    SetInterval(u64),
+    #[cfg(feature = "disk")]
+    ToggleDiskScope,
+    #[cfg(feature = "network")]
+    ToggleNetDisplay,
 }
 
 struct State {
+    config: Config,
     cpu: f32,
     used_mem_mb: u64,
+    // Mémoire disponible (libre + cache récupérable), pour la pression mémoire
+    available_mem_mb: u64,
     total_mem_mb: u64,
+    used_swap_mb: u64,
+    total_swap_mb: u64,
+    uptime_secs: u64,
+    // Mesures récentes, lissées comme les courbes de l'application avant affichage
+    cpu_recent: History,
+    ram_recent: History,
     current_tab: Tab,
+    config_dirty: bool,
+    // Fenêtre réduite à une seule ligne de valeurs
+    tiny: bool,
+    dark_mode: bool,
+    ticks: u64,
+    // Instant du dernier relevé, pour convertir les compteurs réseau en débit
+    last_tick: Instant,
+    // Début du déplacement lancé depuis l'en-tête, et instant du dernier événement `Moved`
+    drag_started: Option<Instant>,
+    last_moved: Option<Instant>,
+    // Position physique du dernier clic sur l'icône (non signalé sous Linux)
+    last_tray_click: Option<(f64, f64)>,
     #[cfg(feature = "network")]
     networks: Networks,
     #[cfg(feature = "network")]
+    rate_tracker: RateTracker,
+    #[cfg(feature = "network")]
     down_mbps: f32,
     #[cfg(feature = "network")]
     up_mbps: f32,
+    #[cfg(feature = "network")]
+    down_recent: History,
+    #[cfg(feature = "network")]
+    up_recent: History,
+    // Débit instantané ou cumul depuis le lancement, au choix de l'utilisateur
+    #[cfg(feature = "network")]
+    net_display: NetDisplay,
+    // Compteurs (reçus, envoyés) au lancement, pour le cumul de la session
+    #[cfg(feature = "network")]
+    session_start_bytes: (u64, u64),
+    // Interfaces comptées au dernier relevé (voir `counted_interfaces`)
+    #[cfg(feature = "network")]
+    counted_interfaces: Vec<String>,
     #[cfg(feature = "battery")]
     battery_percent: f32,
     #[cfg(feature = "battery")]
     battery_charging: bool,
+    #[cfg(feature = "battery")]
+    battery_time_remaining: Option<Duration>,
     #[cfg(feature = "disk")]
     disk_percent: f32,
     #[cfg(feature = "disk")]
-    disk_used_gb: u64,
+    disk_used_gb: f32,
+    #[cfg(feature = "disk")]
+    disk_total_gb: f32,
+    // Détail par volume, affiché à la place du total quand tous les volumes sont choisis
     #[cfg(feature = "disk")]
-    disk_total_gb: u64,
+    disk_volumes: Vec<(String, DiskUsage)>,
     #[cfg(feature = "disk")]
     disks: Disks,
+    #[cfg(feature = "processes")]
+    process_count: usize,
+    #[cfg(feature = "processes")]
+    thread_count: usize,
+    #[cfg(feature = "processes")]
+    top_processes: Vec<(String, f32, u64)>,
+    #[cfg(feature = "temperature")]
+    components: Components,
+    #[cfg(feature = "temperature")]
+    cpu_temperature: Option<f32>,
+    // Utilisation du GPU et de sa mémoire (%)
+    #[cfg(feature = "gpu")]
+    gpu: Option<(f32, f32)>,
+    // Relevés consécutifs au-dessus du seuil d'alerte CPU
+    #[cfg(feature = "alerts")]
+    cpu_over_count: u32,
+    // Niveau de charge affiché par l'icône de la barre de menu
+    tray_level: Option<LoadLevel>,
+    tray: Tray,
+    paused: bool,
+    // Mode clair/sombre du système, pour la couleur d'accent
+    system_theme: theme::Mode,
     sys: System,
 }
 
+// Icône et menu de la barre de menu. Les premières entrées affichent les
+// métriques courantes et ne sont pas cliquables.
+struct Tray {
+    icon: Option<TrayIcon>,
+    // Conservé pour être réaffecté à chaque mise à jour (voir update_tray_menu)
+    #[cfg(target_os = "linux")]
+    menu: Menu,
+    cpu_item: MenuItem,
+    ram_item: MenuItem,
+    #[cfg(feature = "network")]
+    network_item: MenuItem,
+    pause_item: MenuItem,
+    show_item: MenuItem,
+    quit_item: MenuItem,
+}
+
+impl Tray {
+    fn new(show_metrics: bool) -> Self {
+        let menu = Menu::new();
+        let cpu_item = MenuItem::new("CPU : —", false, None);
+        let ram_item = MenuItem::new("RAM : —", false, None);
+        #[cfg(feature = "network")]
+        let network_item = MenuItem::new("Réseau : —", false, None);
+        let pause_item = MenuItem::new("Pause", true, None);
+        let show_item = MenuItem::new("Afficher la fenêtre", true, None);
+        let quit_item = MenuItem::new("Quitter", true, None);
+
+        if show_metrics {
+            menu.append(&cpu_item).ok();
+            menu.append(&ram_item).ok();
+            #[cfg(feature = "network")]
+            menu.append(&network_item).ok();
+            menu.append(&PredefinedMenuItem::separator()).ok();
+        }
+        menu.append(&pause_item).ok();
+        menu.append(&show_item).ok();
+        menu.append(&quit_item).ok();
+
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu.clone()))
+            .with_tooltip("System Monitor - Cliquez pour voir les détails")
+            .with_title("⚡")
+            .build()
+            .ok();
+
+        Self {
+            icon,
+            #[cfg(target_os = "linux")]
+            menu,
+            cpu_item,
+            ram_item,
+            #[cfg(feature = "network")]
+            network_item,
+            pause_item,
+            show_item,
+            quit_item,
+        }
+    }
+}
+
 pub fn main() -> iced::Result {
-    // Créer le menu de la barre de menu
-    let tray_menu = Menu::new();
-    let quit_item = MenuItem::new("Quitter", true, None);
-    tray_menu.append(&quit_item).ok();
-
-    // Créer l'icône de la barre de menu
-    let _tray_icon = TrayIconBuilder::new()
-        .with_menu(Box::new(tray_menu))
-        .with_tooltip("System Monitor - Cliquez pour voir les détails")
-        .with_title("⚡")
-        .build();
+    let config = Config::load();
+    let (x, y) = config
+        .widget_position
+        .unwrap_or((DEFAULT_POSITION.x, DEFAULT_POSITION.y));
 
     iced::application(new, update, view)
         .subscription(subscription)
+        .theme(widget_theme)
         .window(iced::window::Settings {
-            size: iced::Size::new(280.0, 270.0),
-            position: iced::window::Position::Specific(iced::Point::new(
-                1600.0,
-                30.0,
-            )),
+            size: window_size(config.widget_tiny),
+            position: iced::window::Position::Specific(Point::new(x, y)),
             decorations: false,
             transparent: false,
             level: iced::window::Level::AlwaysOnTop,
@@ -88,8 +237,28 @@ pub fn main() -> iced::Result {
         .run()
 }
 
-fn new() -> State {
-    let mut sys = System::new_all();
+const WINDOW_SIZE: Size = Size::new(280.0, 270.0);
+const TINY_WINDOW_SIZE: Size = Size::new(280.0, 24.0);
+const DEFAULT_POSITION: Point = Point::new(1600.0, 30.0);
+// Distance au bord en dessous de laquelle la fenêtre y est collée
+const SNAP_THRESHOLD: f32 = 30.0;
+const TRAY_ICON_SIZE: u32 = 16;
+// Les clics du menu arrivent par un canal : il est lu plus souvent que les métriques
+const TRAY_MENU_POLL: Duration = Duration::from_millis(250);
+// iced ne signale pas la fin d'un déplacement : on la déduit de l'absence
+// d'événement `Moved` pendant ce délai
+const DRAG_END_DELAY: Duration = Duration::from_millis(300);
+
+fn new() -> (State, Task<Message>) {
+    let config = Config::load();
+    let tiny = config.widget_tiny;
+    let dark_mode = config.widget_dark_mode;
+    #[cfg(feature = "network")]
+    let net_display = config.widget_net_display;
+    // Créée une fois la boucle d'événements lancée, sur le thread principal
+    let tray = Tray::new(config.tray_menu_metrics);
+
+    let mut sys = System::new_with_specifics(system_refresh_kind(&config.enabled));
     sys.refresh_cpu_usage();
     sys.refresh_memory();
 
@@ -97,31 +266,84 @@ fn new() -> State {
     let mut networks = Networks::new_with_refreshed_list();
     #[cfg(feature = "network")]
     networks.refresh(true);
+    #[cfg(feature = "network")]
+    let mut rate_tracker = RateTracker::new();
+    #[cfg(feature = "network")]
+    let counted = counted_interfaces(&config, &networks);
+    #[cfg(feature = "network")]
+    rate_tracker.update_for(&networks, Some(&counted));
+    #[cfg(feature = "network")]
+    let session_start_bytes = network_total_bytes(&networks, Some(&counted));
 
     #[cfg(feature = "disk")]
     let disks = Disks::new_with_refreshed_list();
 
     #[cfg(feature = "battery")]
-    let (battery_percent, battery_charging) = get_battery_info();
+    let (battery_percent, battery_charging, battery_time_remaining) = if config.enabled.battery {
+        let battery = get_battery_info_detailed();
+        (battery.percent, battery.charging, battery.time_remaining)
+    } else {
+        (100.0, false, None)
+    };
     
     #[cfg(feature = "disk")]
-    let (disk_percent, disk_used_gb, disk_total_gb) = get_disk_usage(&disks);
+    let DiskUsage {
+        percent: disk_percent,
+        used_gb: disk_used_gb,
+        total_gb: disk_total_gb,
+    } = scoped_disk_usage(&disks, config.disk_scope);
+    #[cfg(feature = "disk")]
+    let disk_volumes = per_disk_usage(&disks);
+
+    let cpu = cpu_usage(&sys, config.cpu_source);
+    let (used_mem_mb, available_mem_mb, total_mem_mb) = memory_usage(&sys);
+    let (used_swap_mb, total_swap_mb) = swap_usage(&sys);
+    let history_len = config.history_len;
 
     let mut state = State {
-        cpu: sys.global_cpu_usage(),
-        used_mem_mb: sys.used_memory() / 1024,
-        total_mem_mb: sys.total_memory() / 1024,
+        config,
+        cpu,
+        used_mem_mb,
+        available_mem_mb,
+        total_mem_mb,
+        used_swap_mb,
+        total_swap_mb,
+        uptime_secs: uptime_seconds(),
+        cpu_recent: History::new(history_len),
+        ram_recent: History::new(history_len),
         current_tab: Tab::System,
+        config_dirty: false,
+        tiny,
+        dark_mode,
+        ticks: 0,
+        last_tick: Instant::now(),
+        drag_started: None,
+        last_moved: None,
+        last_tray_click: None,
         #[cfg(feature = "network")]
         networks,
         #[cfg(feature = "network")]
+        rate_tracker,
+        #[cfg(feature = "network")]
         down_mbps: 0.0,
         #[cfg(feature = "network")]
         up_mbps: 0.0,
+        #[cfg(feature = "network")]
+        down_recent: History::new(history_len),
+        #[cfg(feature = "network")]
+        up_recent: History::new(history_len),
+        #[cfg(feature = "network")]
+        net_display,
+        #[cfg(feature = "network")]
+        session_start_bytes,
+        #[cfg(feature = "network")]
+        counted_interfaces: counted,
         #[cfg(feature = "battery")]
         battery_percent,
         #[cfg(feature = "battery")]
         battery_charging,
+        #[cfg(feature = "battery")]
+        battery_time_remaining,
         #[cfg(feature = "disk")]
         disk_percent,
         #[cfg(feature = "disk")]
@@ -129,85 +351,386 @@ fn new() -> State {
         #[cfg(feature = "disk")]
         disk_total_gb,
         #[cfg(feature = "disk")]
+        disk_volumes,
+        #[cfg(feature = "disk")]
         disks,
+        #[cfg(feature = "processes")]
+        process_count: 0,
+        #[cfg(feature = "processes")]
+        thread_count: 0,
+        #[cfg(feature = "processes")]
+        top_processes: Vec::new(),
+        #[cfg(feature = "temperature")]
+        components: Components::new_with_refreshed_list(),
+        #[cfg(feature = "temperature")]
+        cpu_temperature: None,
+        #[cfg(feature = "gpu")]
+        gpu: gpu_usage(),
+        #[cfg(feature = "alerts")]
+        cpu_over_count: 0,
+        tray_level: None,
+        tray,
+        paused: false,
+        system_theme: theme::Mode::None,
         sys,
     };
 
     state.update_metrics();
-    state
+
+    // Vérifier que la position restaurée est toujours sur un écran branché
+    let (x, y) = state
+        .config
+        .widget_position
+        .unwrap_or((DEFAULT_POSITION.x, DEFAULT_POSITION.y));
+    let position = clamp_to_monitors(Point::new(x, y), window_size(state.tiny), &monitors());
+    let check_position = if position != Point::new(x, y) {
+        window::latest().and_then(move |id| window::move_to(id, position))
+    } else {
+        Task::none()
+    };
+
+    (
+        state,
+        Task::batch([check_position, system::theme().map(Message::SystemThemeChanged)]),
+    )
 }
 
 fn update(state: &mut State, message: Message) -> Task<Message> {
     match message {
         Message::Tick => {
-            state.update_metrics();
+            if !state.paused {
+                state.update_metrics();
+            }
+
+            let snap = state.snap_after_drag();
+
+            if state.config_dirty {
+                state.config_dirty = false;
+                if let Err(err) = state.config.save() {
+                    eprintln!("Impossible d'enregistrer la configuration : {}", err);
+                }
+            }
+
+            if let Some(position) = snap {
+                return window::latest().and_then(move |id| window::move_to(id, position));
+            }
         }
         Message::TabSelected(tab) => {
             state.current_tab = tab;
         }
+        Message::WindowMoved(position) => {
+            state.config.widget_position = Some((position.x, position.y));
+            state.config_dirty = true;
+
+            if state.drag_started.is_some() {
+                state.last_moved = Some(Instant::now());
+            }
+        }
+        Message::SystemThemeChanged(mode) => {
+            state.system_theme = mode;
+        }
+        Message::PollTrayMenu => {
+            while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+                if let TrayIconEvent::Click { position, .. } = event {
+                    state.last_tray_click = Some((position.x, position.y));
+                }
+            }
+
+            while let Ok(event) = MenuEvent::receiver().try_recv() {
+                if event.id == *state.tray.quit_item.id() {
+                    return iced::exit();
+                } else if event.id == *state.tray.show_item.id() {
+                    if let (true, Some((x, y))) =
+                        (state.config.follow_cursor_display, state.last_tray_click)
+                    {
+                        return window::latest().and_then(move |id| {
+                            window::scale_factor(id).map(move |scale| {
+                                let cursor = Point::new(x as f32 / scale, y as f32 / scale);
+                                Message::ShowOnCursorDisplay(id, cursor)
+                            })
+                        });
+                    }
+
+                    return window::latest().and_then(|id| show_window(id, None));
+                } else if event.id == *state.tray.pause_item.id() {
+                    state.toggle_pause();
+                }
+            }
+        }
+        Message::ShowOnCursorDisplay(id, cursor) => {
+            // Sans moniteur sous le curseur, retour à la position enregistrée
+            let position = match monitor_at(cursor, &monitors()) {
+                Some(monitor) => top_right(monitor, window_size(state.tiny)),
+                None => {
+                    let (x, y) = state
+                        .config
+                        .widget_position
+                        .unwrap_or((DEFAULT_POSITION.x, DEFAULT_POSITION.y));
+                    Point::new(x, y)
+                }
+            };
+
+            return show_window(id, Some(position));
+        }
+        Message::ToggleTiny => {
+            state.tiny = !state.tiny;
+            state.config.widget_tiny = state.tiny;
+            state.config_dirty = true;
+
+            let size = window_size(state.tiny);
+            return window::latest().and_then(move |id| window::resize(id, size));
+        }
+        Message::ToggleTheme => {
+            state.dark_mode = !state.dark_mode;
+            state.config.widget_dark_mode = state.dark_mode;
+            state.config_dirty = true;
+        }
+        Message::SetInterval(refresh_ms) => {
+            state.config.refresh_ms = refresh_ms;
+            state.config_dirty = true;
+        }
+        Message::DragWindow => {
+            state.drag_started = Some(Instant::now());
+            state.last_moved = None;
+            return window::latest().and_then(window::drag);
+        }
+        #[cfg(feature = "disk")]
+        Message::ToggleDiskScope => {
+            state.config.disk_scope = state.config.disk_scope.toggled();
+            state.config_dirty = true;
+            // Seuls les chiffres du disque changent : pas de relevé complet hors cadence
+            state.refresh_disk_usage();
+        }
+        #[cfg(feature = "network")]
+        Message::ToggleNetDisplay => {
+            state.net_display = state.net_display.toggled();
+            state.config.widget_net_display = state.net_display;
+            state.config_dirty = true;
+        }
     }
 
     Task::none()
 }
 
-fn subscription(_state: &State) -> Subscription<Message> {
-    time::every(Duration::from_millis(1_000)).map(|_| Message::Tick)
+fn subscription(state: &State) -> Subscription<Message> {
+    Subscription::batch([
+        time::every(Duration::from_millis(state.config.effective_refresh_ms())).map(|_| Message::Tick),
+        time::every(TRAY_MENU_POLL).map(|_| Message::PollTrayMenu),
+        system::theme_changes().map(Message::SystemThemeChanged),
+        event::listen_with(|event, _status, _id| match event {
+            Event::Window(window::Event::Moved(position)) => Some(Message::WindowMoved(position)),
+            _ => None,
+        }),
+    ])
 }
 
-fn view(state: &State) -> Element<'_, Message> {
-    let cpu_percent = state.cpu;
-    let ram_percent = if state.total_mem_mb > 0 {
-        (state.used_mem_mb as f32 / state.total_mem_mb as f32) * 100.0
+// Réaffiche la fenêtre, éventuellement déplacée au préalable
+fn show_window(id: window::Id, position: Option<Point>) -> Task<Message> {
+    let show = window::minimize(id, false).chain(window::gain_focus(id));
+    match position {
+        Some(position) => window::move_to(id, position).chain(show),
+        None => show,
+    }
+}
+
+// Boucle locale, ponts et interfaces de conteneurs, qui gonflent les débits sans
+// correspondre à du trafic réel
+#[cfg(feature = "network")]
+const EXCLUDED_INTERFACES: &[&str] = &["lo", "docker", "veth", "br-"];
+
+// Interfaces comptées : la liste `interfaces` de la configuration telle quelle,
+// sinon toutes celles qui ne sont pas exclues
+#[cfg(feature = "network")]
+fn counted_interfaces(config: &Config, networks: &Networks) -> Vec<String> {
+    match &config.interfaces {
+        Some(names) => names.clone(),
+        None => networks
+            .list()
+            .keys()
+            .filter(|name| !is_excluded_interface(name, EXCLUDED_INTERFACES))
+            .cloned()
+            .collect(),
+    }
+}
+
+// Thème d'iced pour les textes et widgets sans couleur explicite
+fn widget_theme(state: &State) -> Theme {
+    if state.dark_mode {
+        Theme::Dark
     } else {
-        0.0
-    };
+        Theme::Light
+    }
+}
 
-    #[cfg(feature = "battery")]
-    let battery_color = if state.battery_percent > 50.0 {
-        Color::from_rgb8(0x10, 0xb9, 0x81)
-    } else if state.battery_percent > 20.0 {
-        Color::from_rgb8(0xf5, 0x9e, 0x0b)
+fn window_size(tiny: bool) -> Size {
+    if tiny {
+        TINY_WINDOW_SIZE
     } else {
-        Color::from_rgb8(0xef, 0x44, 0x44)
-    };
+        WINDOW_SIZE
+    }
+}
 
+fn view(state: &State) -> Element<'_, Message> {
+    if state.tiny {
+        return tiny_view(state);
+    }
+
+    let accent = state.config.accent.color(state.system_theme);
+    let dark = state.dark_mode;
+    let cpu_percent = state.cpu_percent();
+    let ram_text = state
+        .ram_percent()
+        .map_or_else(|| "indisponible".to_string(), |percent| format!("{:.0}%", percent));
+    let swap_text = state
+        .swap_percent()
+        .map_or_else(|| "N/A".to_string(), |percent| format!("{:.0}%", percent));
+
+    // Vert, puis orange et rouge selon les seuils configurés
+    let cpu_thresholds = state.config.cpu_thresholds;
+    let cpu_color = threshold_color(cpu_percent, cpu_thresholds.warn, cpu_thresholds.crit);
+
+    // Même échelle pour la pression mémoire (part de la RAM non disponible)
+    let memory_thresholds = state.config.memory_thresholds;
+    let ram_color = threshold_color(
+        memory_pressure(state.available_mem_mb, state.total_mem_mb).unwrap_or(0.0),
+        memory_thresholds.warn,
+        memory_thresholds.crit,
+    );
+
+    // Une batterie vide est critique : on mesure la charge manquante
     #[cfg(feature = "battery")]
-    let battery_icon = if state.battery_charging { "⚡" } else { "🔋" };
+    let battery_color = threshold_color(100.0 - state.battery_percent, 50.0, 80.0);
+
     #[cfg(feature = "battery")]
-    let battery_label = format!("{} Batterie", battery_icon);
+    let battery_label = state.config.icons.label(
+        if state.battery_charging { Icon::Charging } else { Icon::Battery },
+        "Batterie",
+    );
+
+    let icons = state.config.icons;
 
     // Créer les boutons d'onglets
     let tabs = row![
-        create_tab_button("Système", Tab::System, state.current_tab),
-        create_tab_button("Réseau", Tab::Network, state.current_tab),
-        create_tab_button("Énergie", Tab::Power, state.current_tab),
-    ]
-    .spacing(4)
-    .padding(8);
+        create_themed_tab_button("Système", Tab::System, state.current_tab, accent, TabSize::Compact, dark, Message::TabSelected),
+        create_themed_tab_button("Réseau", Tab::Network, state.current_tab, accent, TabSize::Compact, dark, Message::TabSelected),
+        create_themed_tab_button("Énergie", Tab::Power, state.current_tab, accent, TabSize::Compact, dark, Message::TabSelected),
+    ];
+
+    #[cfg(feature = "processes")]
+    let tabs = tabs.push(create_themed_tab_button(
+        "Top",
+        Tab::Processes,
+        state.current_tab,
+        accent,
+        TabSize::Compact,
+        dark,
+        Message::TabSelected,
+    ));
+
+    let tabs = tabs.spacing(4).padding(8);
 
     // Contenu selon l'onglet sélectionné
     let content = match state.current_tab {
         Tab::System => {
             let mut col = column![
                 create_metric_row(
-                    "💻 CPU".to_string(),
+                    icons.label(Icon::Cpu, "CPU"),
                     format!("{:.0}%", cpu_percent),
-                    Color::from_rgb8(0x3b, 0x82, 0xf6),
+                    cpu_color,
                 ),
                 create_metric_row(
-                    "🧠 RAM".to_string(),
-                    format!("{:.0}%", ram_percent),
-                    Color::from_rgb8(0xec, 0x48, 0x99),
+                    icons.label(Icon::Memory, "RAM"),
+                    ram_text,
+                    ram_color,
+                ),
+                create_metric_row(
+                    icons.label(Icon::Memory, "Swap"),
+                    swap_text,
+                    Color::from_rgb8(0xa8, 0x55, 0xf7),
+                ),
+                create_metric_row(
+                    "Allumé depuis".to_string(),
+                    format_duration(state.uptime_secs),
+                    Color::from_rgb8(0x64, 0x74, 0x8b),
                 ),
             ]
             .spacing(6);
 
-            #[cfg(feature = "disk")]
+            #[cfg(feature = "gpu")]
             {
                 col = col.push(create_metric_row(
-                    "💾 Stockage".to_string(),
-                    format!("{:.0}% ({}/{}Go)", state.disk_percent, state.disk_used_gb, state.disk_total_gb),
-                    Color::from_rgb8(0xf5, 0x9e, 0x0b),
+                    icons.label(Icon::Gpu, "GPU"),
+                    state.gpu.map_or_else(
+                        || "N/A".to_string(),
+                        |(usage, memory)| format!("{:.0}% · mém. {:.0}%", usage, memory),
+                    ),
+                    Color::from_rgb8(0x22, 0xc5, 0x5e),
+                ));
+            }
+
+            #[cfg(feature = "temperature")]
+            {
+                col = col.push(create_metric_row(
+                    icons.label(Icon::Temperature, "Température"),
+                    state
+                        .cpu_temperature
+                        .map_or_else(|| "N/A".to_string(), |celsius| format!("{:.0} °C", celsius)),
+                    Color::from_rgb8(0xf9, 0x73, 0x16),
+                ));
+            }
+
+            #[cfg(feature = "disk")]
+            if state.config.enabled.disk {
+                let disk_row = |label: String, usage: &DiskUsage| {
+                    create_metric_row(
+                        label,
+                        format!(
+                            "{:.0}% ({}/{})",
+                            usage.percent,
+                            format_gb(usage.used_gb),
+                            format_gb(usage.total_gb)
+                        ),
+                        Color::from_rgb8(0xf5, 0x9e, 0x0b),
+                    )
+                };
+
+                // Tous les volumes : une ligne par point de montage, pour qu'un grand
+                // disque externe ne masque pas un volume système presque plein
+                let rows = match state.config.disk_scope {
+                    DiskScope::All if !state.disk_volumes.is_empty() => state
+                        .disk_volumes
+                        .iter()
+                        .fold(column![].spacing(6), |rows, (mount, usage)| {
+                            rows.push(disk_row(icons.label(Icon::Disk, mount), usage))
+                        }),
+                    scope => {
+                        let label = match scope {
+                            DiskScope::All => icons.label(Icon::Disk, "Stockage"),
+                            DiskScope::System => icons.label(Icon::Disk, "Système"),
+                        };
+                        let usage = DiskUsage {
+                            percent: state.disk_percent,
+                            used_gb: state.disk_used_gb,
+                            total_gb: state.disk_total_gb,
+                        };
+                        column![disk_row(label, &usage)]
+                    }
+                };
+
+                // Un clic bascule entre tous les volumes et le volume système
+                col = col.push(iced::widget::mouse_area(rows).on_press(Message::ToggleDiskScope));
+            }
+
+            #[cfg(feature = "processes")]
+            if state.config.enabled.processes {
+                col = col.push(create_metric_row(
+                    icons.label(Icon::Processes, "Processus"),
+                    if state.thread_count > 0 {
+                        format!("{} ({} threads)", state.process_count, state.thread_count)
+                    } else {
+                        format!("{}", state.process_count)
+                    },
+                    Color::from_rgb8(0x63, 0x66, 0xf1),
                 ));
             }
 
@@ -217,26 +740,34 @@ fn view(state: &State) -> Element<'_, Message> {
             let mut col = column![];
             
             #[cfg(feature = "network")]
-            {
-                col = col.push(create_metric_row(
-                    "📥 Download".to_string(),
-                    format!("{:.1} Mb/s", state.down_mbps),
-                    Color::from_rgb8(0x10, 0xb9, 0x81),
-                ))
-                .push(create_metric_row(
-                    "📤 Upload".to_string(),
-                    format!("{:.1} Mb/s", state.up_mbps),
-                    Color::from_rgb8(0x06, 0x99, 0x68),
-                ));
+            if state.config.enabled.network {
+                let (down, up) = state.network_values();
+                // Un clic sur les lignes bascule entre débit et cumul de la session
+                col = col.push(
+                    iced::widget::mouse_area(
+                        column![
+                            create_metric_row(
+                                icons.label(Icon::Download, "Download"),
+                                down,
+                                Color::from_rgb8(0x10, 0xb9, 0x81),
+                            ),
+                            create_metric_row(
+                                icons.label(Icon::Upload, "Upload"),
+                                up,
+                                Color::from_rgb8(0x06, 0x99, 0x68),
+                            ),
+                        ]
+                        .spacing(6),
+                    )
+                    .on_press(Message::ToggleNetDisplay),
+                );
+            } else {
+                col = col.push(disabled_placeholder("Module réseau désactivé"));
             }
 
             #[cfg(not(feature = "network"))]
             {
-                col = col.push(
-                    container(text("Module réseau non activé").size(12))
-                        .padding(20)
-                        .center(Length::Fill)
-                );
+                col = col.push(disabled_placeholder("Module réseau non activé"));
             }
 
             col.spacing(6)
@@ -245,21 +776,45 @@ fn view(state: &State) -> Element<'_, Message> {
             let mut col = column![];
 
             #[cfg(feature = "battery")]
-            {
-                col = col.push(create_metric_row(
-                    battery_label,
-                    format!("{:.0}%", state.battery_percent),
-                    battery_color,
-                ));
+            if state.config.enabled.battery {
+                // Autonomie restante, ou durée avant charge complète
+                let value = match state.battery_time_remaining {
+                    Some(remaining) => {
+                        format!("{:.0}% · {}", state.battery_percent, format_eta(remaining))
+                    }
+                    None => format!("{:.0}%", state.battery_percent),
+                };
+                col = col.push(create_metric_row(battery_label, value, battery_color));
+            } else {
+                col = col.push(disabled_placeholder("Module batterie désactivé"));
             }
 
             #[cfg(not(feature = "battery"))]
             {
-                col = col.push(
-                    container(text("Module batterie non activé").size(12))
-                        .padding(20)
-                        .center(Length::Fill)
-                );
+                col = col.push(disabled_placeholder("Module batterie non activé"));
+            }
+
+            // Un rafraîchissement plus lent économise la batterie
+            col = col.push(interval_choices(state, accent));
+
+            col.spacing(6)
+        }
+        #[cfg(feature = "processes")]
+        Tab::Processes => {
+            let mut col = column![];
+
+            if !state.config.enabled.processes {
+                col = col.push(disabled_placeholder("Module processus désactivé"));
+            } else if state.top_processes.is_empty() {
+                col = col.push(disabled_placeholder("Mesure en cours…"));
+            }
+
+            for (name, cpu, memory_mb) in &state.top_processes {
+                col = col.push(create_metric_row(
+                    name.clone(),
+                    format!("{:.0}% · {} Mo", cpu, memory_mb),
+                    Color::from_rgb8(0x63, 0x66, 0xf1),
+                ));
             }
 
             col.spacing(6)
@@ -268,15 +823,27 @@ fn view(state: &State) -> Element<'_, Message> {
 
     container(
         column![
+            // L'en-tête sert de poignée pour déplacer la fenêtre sans décorations
             container(
-                text("System Monitor")
-                    .size(14)
-                    .color(Color::WHITE)
+                row![
+                    iced::widget::mouse_area(
+                        container(
+                            text("System Monitor")
+                                .size(14)
+                                .color(Color::WHITE)
+                        )
+                        .width(Length::Fill)
+                    )
+                    .on_press(Message::DragWindow),
+                    header_button(if dark { "☀" } else { "☾" }, Message::ToggleTheme),
+                    header_button("▁", Message::ToggleTiny),
+                ]
+                .align_y(iced::Alignment::Center)
             )
             .padding(8)
-            .style(|_theme: &Theme| {
+            .style(move |_theme: &Theme| {
                 container::Style {
-                    background: Some(Color::from_rgb8(0x1f, 0x29, 0x37).into()),
+                    background: Some(header_color(dark).into()),
                     ..Default::default()
                 }
             })
@@ -284,16 +851,23 @@ fn view(state: &State) -> Element<'_, Message> {
             
             tabs,
             
-            content.padding(10)
+            // Les onglets chargés (un volume par ligne) défilent plutôt que de déborder
+            iced::widget::scrollable(content.padding(10)).height(Length::Fill)
         ]
         .spacing(0)
     )
-    .style(|_theme: &Theme| {
+    .style(move |_theme: &Theme| {
+        let (background, border) = if dark {
+            (Color::from_rgb8(0x11, 0x18, 0x27), Color::from_rgb8(0x37, 0x41, 0x51))
+        } else {
+            (Color::from_rgb8(0xf3, 0xf4, 0xf6), Color::from_rgb8(0xd1, 0xd5, 0xdb))
+        };
+
         container::Style {
-            background: Some(Color::from_rgb8(0xf3, 0xf4, 0xf6).into()),
+            background: Some(background.into()),
             border: Border {
                 radius: 12.0.into(),
-                color: Color::from_rgb8(0xd1, 0xd5, 0xdb),
+                color: border,
                 width: 1.0,
             },
             shadow: Shadow {
@@ -307,110 +881,390 @@ fn view(state: &State) -> Element<'_, Message> {
     .into()
 }
 
-fn create_metric_row(
-    label: String,
-    value: String,
-    color: Color,
-) -> Element<'static, Message> {
+// Mode réduit : une seule ligne, « CPU 34% RAM 61% ↓2.1 82% », déplaçable comme l'en-tête.
+// Un clic droit bascule le réseau entre débit et cumul (« ↓1.20 Go ↑340.0 Mo »).
+fn tiny_view(state: &State) -> Element<'_, Message> {
+    let ram_text = state
+        .ram_percent()
+        .map_or_else(|| "—".to_string(), |percent| format!("{:.0}%", percent));
+
+    let line = format!("CPU {:.0}% RAM {}", state.cpu_percent(), ram_text);
+    let dark = state.dark_mode;
+
+    #[cfg(feature = "network")]
+    let line = if state.config.enabled.network {
+        match state.net_display {
+            NetDisplay::Rate => format!("{} ↓{:.1}", line, state.rates().0),
+            NetDisplay::Totals => {
+                let (down, up) = state.network_values();
+                format!("{} ↓{} ↑{}", line, down, up)
+            }
+        }
+    } else {
+        line
+    };
+
+    #[cfg(feature = "battery")]
+    let line = if state.config.enabled.battery {
+        format!("{} {:.0}%", line, state.battery_percent)
+    } else {
+        line
+    };
+
+    let handle = iced::widget::mouse_area(
+        container(text(line).size(12).color(Color::WHITE)).width(Length::Fill),
+    )
+    .on_press(Message::DragWindow);
+
+    #[cfg(feature = "network")]
+    let handle = handle.on_right_press(Message::ToggleNetDisplay);
+
     container(
         row![
-            text(label)
-                .size(13)
-                .color(Color::WHITE)
-                .width(Length::Fill),
-            text(value)
-                .size(16)
-                .color(Color::WHITE)
+            handle,
+            header_button("▢", Message::ToggleTiny),
         ]
-        .align_y(iced::Alignment::Center)
-        .spacing(10)
-        .padding(8)
+        .align_y(iced::Alignment::Center),
     )
-    .style(move |_theme: &Theme| {
-        container::Style {
-            background: Some(color.into()),
-            border: Border {
-                radius: 8.0.into(),
-                ..Default::default()
-            },
-            ..Default::default()
-        }
-    })
+    .padding([0, 8])
+    .center_y(Length::Fill)
     .width(Length::Fill)
+    .style(move |_theme: &Theme| container::Style {
+        background: Some(header_color(dark).into()),
+        ..Default::default()
+    })
     .into()
 }
 
-fn create_tab_button(label: &'static str, tab: Tab, current_tab: Tab) -> Element<'static, Message> {
-    let is_active = tab == current_tab;
-    
-    button(
-        text(label)
-            .size(12)
-            .color(if is_active {
-                Color::WHITE
-            } else {
-                Color::from_rgb8(0x6b, 0x7c, 0x93)
-            })
-    )
-    .padding([6, 12])
-    .style(move |_theme: &Theme, _status| {
-        button::Style {
-            background: Some(if is_active {
-                Color::from_rgb8(0x3b, 0x82, 0xf6).into()
-            } else {
-                Color::from_rgb8(0xe5, 0xe7, 0xeb).into()
-            }),
-            border: Border {
-                radius: 6.0.into(),
-                ..Default::default()
-            },
-            text_color: if is_active {
-                Color::WHITE
-            } else {
-                Color::from_rgb8(0x6b, 0x7c, 0x93)
-            },
+// Fond de l'en-tête et de la ligne du mode réduit
+fn header_color(dark: bool) -> Color {
+    if dark {
+        Color::from_rgb8(0x03, 0x07, 0x12)
+    } else {
+        Color::from_rgb8(0x1f, 0x29, 0x37)
+    }
+}
+
+fn header_button(label: &'static str, on_press: Message) -> Element<'static, Message> {
+    button(text(label).size(12).color(Color::WHITE))
+        .padding([0, 4])
+        .style(|_theme: &Theme, _status| button::Style {
+            background: None,
             ..Default::default()
-        }
-    })
-    .on_press(Message::TabSelected(tab))
-    .into()
+        })
+        .on_press(on_press)
+        .into()
+}
+
+fn interval_choices(state: &State, accent: Color) -> Element<'_, Message> {
+    State::INTERVAL_CHOICES
+        .iter()
+        .fold(row![text("Rafraîchissement").size(12).width(Length::Fill)], |choices, &ms| {
+            let selected = state.config.refresh_ms == ms;
+            let color = match (selected, state.dark_mode) {
+                (true, _) => Color::WHITE,
+                (false, true) => Color::from_rgb8(0x9c, 0xa3, 0xaf),
+                (false, false) => Color::from_rgb8(0x4b, 0x55, 0x63),
+            };
+            choices.push(
+                button(text(format_interval(ms)).size(11).color(color))
+                .padding([1, 5])
+                .style(move |_theme: &Theme, _status| button::Style {
+                    background: selected.then(|| accent.into()),
+                    border: Border {
+                        radius: 4.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .on_press(Message::SetInterval(ms)),
+            )
+        })
+        .spacing(2)
+        .align_y(iced::Alignment::Center)
+        .into()
+}
+
+fn disabled_placeholder(label: &'static str) -> Element<'static, Message> {
+    container(text(label).size(12))
+        .padding(20)
+        .center(Length::Fill)
+        .into()
 }
 
 impl State {
+    // Valeur lissée comme dans l'application (la mesure elle-même sans lissage)
+    fn displayed(&self, recent: &History, current: f32) -> f32 {
+        smoothed_latest(recent, self.config.smoothing).unwrap_or(current)
+    }
+
+    fn cpu_percent(&self) -> f32 {
+        self.displayed(&self.cpu_recent, self.cpu)
+    }
+
+    fn raw_ram_percent(&self) -> Option<f32> {
+        memory_available(self.total_mem_mb)
+            .then(|| (self.used_mem_mb as f32 / self.total_mem_mb as f32) * 100.0)
+    }
+
+    fn ram_percent(&self) -> Option<f32> {
+        self.raw_ram_percent()
+            .map(|percent| self.displayed(&self.ram_recent, percent))
+    }
+
+    // Débits (descendant, montant) lissés, en Mb/s
+    #[cfg(feature = "network")]
+    fn rates(&self) -> (f32, f32) {
+        (
+            self.displayed(&self.down_recent, self.down_mbps),
+            self.displayed(&self.up_recent, self.up_mbps),
+        )
+    }
+
+    // `None` quand le swap est désactivé
+    fn swap_percent(&self) -> Option<f32> {
+        (self.total_swap_mb > 0)
+            .then(|| (self.used_swap_mb as f32 / self.total_swap_mb as f32) * 100.0)
+    }
+
+    // Position collée aux bords du moniteur où la fenêtre a été lâchée, si elle diffère.
+    // Un clic sur l'en-tête sans déplacement termine aussi le glisser, pour qu'un
+    // `Moved` ultérieur (repositionnement par le système) ne soit pas pris pour un lâcher.
+    fn snap_after_drag(&mut self) -> Option<Point> {
+        let started = self.drag_started?;
+        if self.last_moved.unwrap_or(started).elapsed() < DRAG_END_DELAY {
+            return None;
+        }
+
+        self.drag_started = None;
+        self.last_moved.take()?;
+
+        let (x, y) = self.config.widget_position?;
+        let position = Point::new(x, y);
+        let size = window_size(self.tiny);
+        let center = Point::new(position.x + size.width / 2.0, position.y + size.height / 2.0);
+        let monitors = monitors();
+        let monitor = monitor_at(center, &monitors).or_else(|| monitor_at(position, &monitors))?;
+        let snapped = snap_to_edges(position, size, monitor, SNAP_THRESHOLD);
+
+        if snapped == position {
+            return None;
+        }
+
+        self.config.widget_position = Some((snapped.x, snapped.y));
+        self.config_dirty = true;
+        Some(snapped)
+    }
+
+    #[cfg(feature = "processes")]
+    const PROCESS_REFRESH_TICKS: u64 = 5;
+    #[cfg(feature = "processes")]
+    const TOP_PROCESSES: usize = 5;
+    const INTERVAL_CHOICES: [u64; 4] = [500, 1_000, 2_000, 5_000];
+
+    // Valeurs (descendante, montante) de la ligne réseau selon l'affichage choisi
+    #[cfg(feature = "network")]
+    fn network_values(&self) -> (String, String) {
+        match self.net_display {
+            NetDisplay::Rate => {
+                let (down, up) = self.rates();
+                (format!("{:.1} Mb/s", down), format!("{:.1} Mb/s", up))
+            }
+            NetDisplay::Totals => {
+                let (rx, tx) = network_total_bytes(&self.networks, Some(&self.counted_interfaces));
+                let (start_rx, start_tx) = self.session_start_bytes;
+                (
+                    format_bytes(rx.saturating_sub(start_rx)),
+                    format_bytes(tx.saturating_sub(start_tx)),
+                )
+            }
+        }
+    }
+
     fn update_metrics(&mut self) {
+        self.ticks += 1;
+        let now = Instant::now();
+        #[cfg(feature = "network")]
+        let elapsed_secs = now.duration_since(self.last_tick).as_secs_f32().max(0.001);
+        self.last_tick = now;
         self.sys.refresh_cpu_usage();
         self.sys.refresh_memory();
         
         #[cfg(feature = "network")]
-        self.networks.refresh(true);
+        if self.config.enabled.network {
+            self.networks.refresh(true);
+        }
         
         #[cfg(feature = "disk")]
-        self.disks.refresh(true);
+        if self.config.enabled.disk {
+            self.disks.refresh(true);
+        }
 
-        self.cpu = self.sys.global_cpu_usage();
-        self.used_mem_mb = self.sys.used_memory() / 1024;
-        self.total_mem_mb = self.sys.total_memory() / 1024;
+        self.cpu = cpu_usage(&self.sys, self.config.cpu_source);
+        (self.used_mem_mb, self.available_mem_mb, self.total_mem_mb) = memory_usage(&self.sys);
+        (self.used_swap_mb, self.total_swap_mb) = swap_usage(&self.sys);
+        self.uptime_secs = uptime_seconds();
+        self.cpu_recent.push(self.cpu);
+        if let Some(percent) = self.raw_ram_percent() {
+            self.ram_recent.push(percent);
+        }
 
-        #[cfg(feature = "network")]
+        #[cfg(feature = "gpu")]
         {
-            let (delta_rx, delta_tx) = network_deltas(&self.networks);
-            self.down_mbps = delta_rx as f32 * 8.0 / 1_000_000.0;
-            self.up_mbps = delta_tx as f32 * 8.0 / 1_000_000.0;
+            self.gpu = gpu_usage();
+        }
+
+        #[cfg(feature = "alerts")]
+        if self.config.cpu_alert.track(&mut self.cpu_over_count, self.cpu) {
+            let (percent, ticks) = (self.cpu, self.config.cpu_alert.ticks);
+            // L'envoi peut bloquer sur le bus de notifications : hors du fil de l'interface
+            std::thread::spawn(move || {
+                if let Err(err) = notify_cpu(percent, ticks) {
+                    eprintln!("Notification impossible : {}", err);
+                }
+            });
+        }
+
+        #[cfg(feature = "network")]
+        if self.config.enabled.network {
+            self.counted_interfaces = counted_interfaces(&self.config, &self.networks);
+            let (delta_rx, delta_tx) =
+                self.rate_tracker.update_for(&self.networks, Some(&self.counted_interfaces));
+            self.down_mbps = delta_rx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+            self.up_mbps = delta_tx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+            self.down_recent.push(self.down_mbps);
+            self.up_recent.push(self.up_mbps);
         }
 
         #[cfg(feature = "battery")]
+        if self.config.enabled.battery {
+            let battery = get_battery_info_detailed();
+            self.battery_percent = battery.percent;
+            self.battery_charging = battery.charging;
+            self.battery_time_remaining = battery.time_remaining;
+        }
+
+        // La liste des processus est rafraîchie moins souvent (dès le premier appel),
+        // sauf quand l'onglet Top est affiché
+        #[cfg(feature = "processes")]
+        if self.config.enabled.processes
+            && (self.ticks == 1
+                || self.ticks.is_multiple_of(Self::PROCESS_REFRESH_TICKS)
+                || self.current_tab == Tab::Processes)
         {
-            let (battery_percent, battery_charging) = get_battery_info();
-            self.battery_percent = battery_percent;
-            self.battery_charging = battery_charging;
+            refresh_processes(&mut self.sys);
+            (self.process_count, self.thread_count) = process_counts(&self.sys);
+            self.top_processes = top_processes(&self.sys, Self::TOP_PROCESSES);
         }
 
         #[cfg(feature = "disk")]
+        if self.config.enabled.disk {
+            self.refresh_disk_usage();
+            self.disk_volumes = per_disk_usage(&self.disks);
+        }
+
+        // Capteur global du processeur, sinon le cœur le plus chaud
+        #[cfg(feature = "temperature")]
+        {
+            self.components.refresh(false);
+            self.cpu_temperature = cpu_temperature(&self.components)
+                .or_else(|| hottest_core(&per_core_temperatures(&self.components)));
+        }
+
+        self.update_tray_color();
+        self.update_tray_menu();
+    }
+
+    // Occupation du stockage selon la portée choisie, depuis le dernier relevé des disques
+    #[cfg(feature = "disk")]
+    fn refresh_disk_usage(&mut self) {
+        let usage = scoped_disk_usage(&self.disks, self.config.disk_scope);
+        self.disk_percent = usage.percent;
+        self.disk_used_gb = usage.used_gb;
+        self.disk_total_gb = usage.total_gb;
+    }
+
+    // Ne redessine l'icône que lorsque le niveau de charge change
+    fn update_tray_color(&mut self) {
+        let snapshot = Snapshot {
+            cpu: self.cpu,
+            used_mem_mb: self.used_mem_mb,
+            total_mem_mb: self.total_mem_mb,
+            ..Snapshot::default()
+        };
+
+        #[cfg(feature = "disk")]
+        let snapshot = Snapshot {
+            disk: self.config.enabled.disk.then_some(DiskUsage {
+                percent: self.disk_percent,
+                used_gb: self.disk_used_gb,
+                total_gb: self.disk_total_gb,
+            }),
+            ..snapshot
+        };
+
+        #[cfg(feature = "temperature")]
+        let snapshot = Snapshot {
+            cpu_temperature: self.cpu_temperature,
+            ..snapshot
+        };
+
+        let level = LoadLevel::from_value(tray_color_value(&snapshot, self.config.tray_color_source));
+        if self.tray_level == Some(level) {
+            return;
+        }
+        self.tray_level = Some(level);
+
+        let icon = tray_icon::Icon::from_rgba(level_icon_rgba(level, TRAY_ICON_SIZE), TRAY_ICON_SIZE, TRAY_ICON_SIZE);
+        if let (Some(tray), Ok(icon)) = (&self.tray.icon, icon) {
+            tray.set_icon(Some(icon)).ok();
+        }
+    }
+
+    fn update_tray_menu(&self) {
+        if !self.config.tray_menu_metrics {
+            return;
+        }
+
+        let ram_text = self
+            .ram_percent()
+            .map_or_else(|| "indisponible".to_string(), |percent| format!("{:.0} %", percent));
+
+        self.tray.cpu_item.set_text(format!("CPU : {:.0} %", self.cpu_percent()));
+        self.tray.ram_item.set_text(format!("RAM : {}", ram_text));
+        #[cfg(feature = "network")]
         {
-            let (disk_percent, disk_used_gb, disk_total_gb) = get_disk_usage(&self.disks);
-            self.disk_percent = disk_percent;
-            self.disk_used_gb = disk_used_gb;
-            self.disk_total_gb = disk_total_gb;
+            let (down, up) = self.rates();
+            self.tray
+                .network_item
+                .set_text(format!("Réseau : ↓{:.1} ↑{:.1} Mb/s", down, up));
+        }
+
+        // Sous Linux (libappindicator), un menu déjà affecté n'est pas toujours
+        // redessiné après modification : on le réaffecte à l'icône
+        #[cfg(target_os = "linux")]
+        if let Some(icon) = &self.tray.icon {
+            icon.set_menu(Some(Box::new(self.tray.menu.clone())));
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.tray
+            .pause_item
+            .set_text(if self.paused { "Reprendre" } else { "Pause" });
+
+        // À la reprise, les compteurs réseau et l'horloge des débits repartent de
+        // maintenant : ni le trafic ni la durée de la pause ne comptent pour le
+        // premier relevé
+        if !self.paused {
+            self.last_tick = Instant::now();
+        }
+        #[cfg(feature = "network")]
+        if !self.paused && self.config.enabled.network {
+            self.networks.refresh(true);
+            self.counted_interfaces = counted_interfaces(&self.config, &self.networks);
+            self.rate_tracker.update_for(&self.networks, Some(&self.counted_interfaces));
         }
     }
 }
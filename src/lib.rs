@@ -1,144 +1,4556 @@
 // Bibliothèque partagée entre l'application complète et le widget
 
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+pub mod config {
+    use serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct Config {
+        // Intervalle de rafraîchissement des métriques
+        pub refresh_ms: u64,
+        // Nombre de points conservés par graphique
+        pub history_len: usize,
+        // Historiques enregistrés à la fermeture et rechargés au lancement
+        pub persist_history: bool,
+        // Accessibilité : les graphiques ne sont mis à jour que toutes les
+        // quelques secondes, les valeurs numériques restent à la seconde.
+        pub reduce_motion: bool,
+        pub chart_baseline: BaselineMode,
+        pub chart_fill: FillStyle,
+        // Lissage commun aux graphiques et aux valeurs affichées (ancienne clé `chart_style`)
+        #[serde(alias = "chart_style")]
+        pub smoothing: Smoothing,
+        // Durée d'un point des graphiques (ms), quand elle dépasse l'intervalle de mesure
+        pub chart_point_ms: Option<u64>,
+        pub chart_aggregation: PointAggregation,
+        // Dernière position connue du widget (coordonnées logiques)
+        pub widget_position: Option<(f32, f32)>,
+        // Widget réduit à une ligne de valeurs
+        pub widget_tiny: bool,
+        // Widget en thème sombre
+        pub widget_dark_mode: bool,
+        // À l'ouverture depuis l'icône, place le widget sur le moniteur du curseur
+        pub follow_cursor_display: bool,
+        // Réseau du widget : débit instantané ou cumul depuis le lancement
+        pub widget_net_display: NetDisplay,
+        pub disk_scope: DiskScope,
+        pub cpu_source: CpuSource,
+        pub enabled: EnabledMetrics,
+        pub icons: IconSet,
+        // Si renseignée, seules ces interfaces réseau sont comptées
+        pub interfaces: Option<Vec<String>>,
+        // Nombre d'interfaces détaillées, les autres sont cumulées
+        pub max_interfaces: usize,
+        // Débit maximal du lien (Mbps), pour afficher le taux d'utilisation du réseau
+        pub net_max_mbps: Option<f32>,
+        pub tray_color_source: TrayColorSource,
+        // Affiche CPU, RAM et réseau en tête du menu de l'icône du widget
+        pub tray_menu_metrics: bool,
+        // Nombre d'intervalles sans nouvelle mesure avant de signaler des données périmées
+        pub stale_after_intervals: u32,
+        // Seuil (%) au-delà duquel un cœur est compté comme occupé
+        pub busy_core_threshold: f32,
+        pub accent: AccentSource,
+        // Alerte quand une valeur dépasse la référence apprise de k écarts-types
+        pub baseline_sigmas: f32,
+        // Seuils d'utilisation CPU (orange, puis rouge)
+        pub cpu_thresholds: Thresholds,
+        // Seuils de pression mémoire (orange, puis rouge)
+        pub memory_thresholds: Thresholds,
+        // Notification quand le CPU reste saturé (feature `alerts`)
+        pub cpu_alert: SustainedAlert,
+        // Graphiques supplémentaires, par nom du registre des métriques
+        pub charts: Vec<String>,
+        // Deux métriques du registre superposées, chacune à sa propre échelle
+        pub correlation: Option<(String, String)>,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                refresh_ms: 1_000,
+                history_len: 120,
+                persist_history: false,
+                reduce_motion: false,
+                chart_baseline: BaselineMode::default(),
+                chart_fill: FillStyle::default(),
+                smoothing: Smoothing::default(),
+                chart_point_ms: None,
+                chart_aggregation: PointAggregation::default(),
+                widget_position: None,
+                widget_tiny: false,
+                widget_dark_mode: false,
+                follow_cursor_display: false,
+                widget_net_display: NetDisplay::default(),
+                disk_scope: DiskScope::default(),
+                cpu_source: CpuSource::default(),
+                enabled: EnabledMetrics::default(),
+                icons: IconSet::default(),
+                interfaces: None,
+                max_interfaces: 4,
+                net_max_mbps: None,
+                tray_color_source: TrayColorSource::default(),
+                tray_menu_metrics: true,
+                stale_after_intervals: 3,
+                busy_core_threshold: 80.0,
+                accent: AccentSource::default(),
+                baseline_sigmas: 3.0,
+                cpu_thresholds: Thresholds {
+                    warn: 70.0,
+                    crit: 90.0,
+                },
+                memory_thresholds: Thresholds::default(),
+                cpu_alert: SustainedAlert::default(),
+                charts: Vec::new(),
+                correlation: None,
+            }
+        }
+    }
+
+    // Valeur placée en bas des graphiques : zéro, ou le minimum des données
+    // pour mieux voir les variations d'une métrique qui reste dans une bande étroite.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum BaselineMode {
+        #[default]
+        Zero,
+        Min,
+    }
+
+    // Remplissage sous la courbe des graphiques : aucun, uni (translucide), ou
+    // dégradé de la couleur de la courbe vers la transparence en bas du graphique.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum FillStyle {
+        #[default]
+        None,
+        Solid,
+        Gradient,
+    }
+
+    // Mesures regroupées dans un même point de graphique : la dernière, ou leur
+    // minimum, moyenne ou maximum (un pic bref reste alors visible).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum PointAggregation {
+        #[default]
+        Last,
+        Min,
+        Avg,
+        Max,
+    }
+
+    // Lissage des séries affichées, appliqué au même endroit pour une courbe et
+    // la valeur qui l'accompagne. Les noms `raw` et `smoothed_only` de l'ancienne
+    // option `chart_style` restent acceptés.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Smoothing {
+        // Échantillons bruts
+        #[default]
+        #[serde(alias = "raw")]
+        None,
+        // Moyenne glissante sur N points (tendance des métriques lentes comme la RAM)
+        #[serde(alias = "smoothed_only")]
+        MovingAverage(usize),
+        // Moyenne exponentielle de coefficient alpha (0 à 1, 1 = pas de lissage)
+        Exponential(f32),
+        // Pics écrêtés au centile donné de la série (0 à 100)
+        PercentileClamp(f32),
+    }
+
+    // Seuils d'alerte d'une métrique où une valeur élevée est mauvaise
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct Thresholds {
+        pub warn: f32,
+        pub crit: f32,
+    }
+
+    impl Thresholds {
+        pub fn level(&self, value: f32) -> crate::LoadLevel {
+            if value >= self.crit {
+                crate::LoadLevel::Critical
+            } else if value >= self.warn {
+                crate::LoadLevel::Elevated
+            } else {
+                crate::LoadLevel::Normal
+            }
+        }
+    }
+
+    impl Default for Thresholds {
+        fn default() -> Self {
+            Self {
+                warn: 80.0,
+                crit: 90.0,
+            }
+        }
+    }
+
+    // Alerte quand une valeur reste au-dessus de `threshold` pendant `ticks` relevés
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct SustainedAlert {
+        pub threshold: f32,
+        pub ticks: u32,
+    }
+
+    impl SustainedAlert {
+        /// Compte les relevés consécutifs au-dessus du seuil (remis à zéro dès qu'il
+        /// redescend) et indique s'il faut alerter : une seule fois par dépassement,
+        /// au `ticks`-ième relevé.
+        pub fn track(&self, count: &mut u32, value: f32) -> bool {
+            if value > self.threshold {
+                *count = count.saturating_add(1);
+                *count == self.ticks.max(1)
+            } else {
+                *count = 0;
+                false
+            }
+        }
+    }
+
+    impl Default for SustainedAlert {
+        fn default() -> Self {
+            Self {
+                threshold: 90.0,
+                ticks: 10,
+            }
+        }
+    }
+
+    // Valeur CPU affichée et tracée : la valeur globale de sysinfo, ou la moyenne
+    // des cœurs. Elles peuvent différer selon la plateforme : la valeur globale vient
+    // de compteurs système distincts de ceux de chaque cœur (temps d'attente E/S,
+    // cœurs hors ligne ou arrondis comptés différemment).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum CpuSource {
+        #[default]
+        Global,
+        CoreAverage,
+    }
+
+    // Volumes pris en compte par l'indicateur de stockage
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum DiskScope {
+        #[default]
+        All,
+        System,
+    }
+
+    impl DiskScope {
+        pub fn toggled(self) -> Self {
+            match self {
+                DiskScope::All => DiskScope::System,
+                DiskScope::System => DiskScope::All,
+            }
+        }
+    }
+
+    // Affichage du réseau dans le widget
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum NetDisplay {
+        #[default]
+        Rate,
+        Totals,
+    }
+
+    impl NetDisplay {
+        pub fn toggled(self) -> Self {
+            match self {
+                NetDisplay::Rate => NetDisplay::Totals,
+                NetDisplay::Totals => NetDisplay::Rate,
+            }
+        }
+    }
+
+    // Icônes des libellés : les emoji s'affichent en carrés vides avec
+    // certaines polices Linux, d'où une variante ASCII et une variante sans icône.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum IconSet {
+        #[default]
+        Emoji,
+        Ascii,
+        None,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Icon {
+        App,
+        Cpu,
+        Memory,
+        Network,
+        Download,
+        Upload,
+        Battery,
+        Charging,
+        Disk,
+        Processes,
+        Temperature,
+        Gpu,
+        Settings,
+        Pause,
+        Wired,
+        Wireless,
+        Vpn,
+    }
+
+    impl IconSet {
+        pub fn icon(self, icon: Icon) -> &'static str {
+            match self {
+                IconSet::Emoji => match icon {
+                    Icon::App | Icon::Charging => "⚡",
+                    Icon::Cpu => "💻",
+                    Icon::Memory => "🧠",
+                    Icon::Network => "🌐",
+                    Icon::Download => "📥",
+                    Icon::Upload => "📤",
+                    Icon::Battery => "🔋",
+                    Icon::Disk => "💾",
+                    Icon::Processes => "⚙️",
+                    Icon::Temperature => "🌡️",
+                    Icon::Gpu => "🎮",
+                    Icon::Settings => "⚙",
+                    Icon::Pause => "⏸",
+                    Icon::Wired => "🔌",
+                    Icon::Wireless => "📶",
+                    Icon::Vpn => "🔒",
+                },
+                IconSet::Ascii => match icon {
+                    Icon::App => "",
+                    Icon::Cpu => "[CPU]",
+                    Icon::Memory => "[RAM]",
+                    Icon::Network => "[NET]",
+                    Icon::Download => "[v]",
+                    Icon::Upload => "[^]",
+                    Icon::Battery => "[BAT]",
+                    Icon::Charging => "[AC]",
+                    Icon::Disk => "[HDD]",
+                    Icon::Processes => "[PID]",
+                    Icon::Temperature => "[TMP]",
+                    Icon::Gpu => "[GPU]",
+                    Icon::Settings => "[*]",
+                    Icon::Pause => "[||]",
+                    Icon::Wired => "[ETH]",
+                    Icon::Wireless => "[WIFI]",
+                    Icon::Vpn => "[VPN]",
+                },
+                IconSet::None => "",
+            }
+        }
+
+        /// Libellé précédé de l'icône, ou seul si le jeu n'en a pas.
+        pub fn label(self, icon: Icon, text: &str) -> String {
+            match self.icon(icon) {
+                "" => text.to_string(),
+                icon => format!("{} {}", icon, text),
+            }
+        }
+    }
+
+    // Métrique qui colore l'icône du widget dans la barre de menu
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum TrayColorSource {
+        #[default]
+        Cpu,
+        Ram,
+        // Le plus chargé de CPU, RAM et stockage
+        Hottest,
+        Temperature,
+    }
+
+    // Activation des modules à l'exécution, dans la limite des features compilées :
+    // un module désactivé n'est ni rafraîchi ni affiché.
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct EnabledMetrics {
+        pub battery: bool,
+        pub network: bool,
+        pub disk: bool,
+        pub processes: bool,
+    }
+
+    // Couleur d'accent (carte CPU, onglet et boutons actifs). iced ne donne
+    // accès qu'au mode clair/sombre du système, pas à sa couleur d'accent :
+    // `System` choisit un bleu adapté à ce mode.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum AccentSource {
+        #[default]
+        System,
+        Fixed([u8; 3]),
+    }
+
+    impl AccentSource {
+        pub fn color(self, mode: iced::theme::Mode) -> iced::Color {
+            match self {
+                AccentSource::Fixed([r, g, b]) => iced::Color::from_rgb8(r, g, b),
+                AccentSource::System => match mode {
+                    iced::theme::Mode::Dark => iced::Color::from_rgb8(0x60, 0xa5, 0xfa),
+                    // Mode inconnu : bleu d'origine
+                    iced::theme::Mode::Light | iced::theme::Mode::None => {
+                        iced::Color::from_rgb8(0x3b, 0x82, 0xf6)
+                    }
+                },
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Module {
+        Battery,
+        Network,
+        Disk,
+        Processes,
+    }
+
+    impl EnabledMetrics {
+        pub fn is_enabled(&self, module: Module) -> bool {
+            match module {
+                Module::Battery => self.battery,
+                Module::Network => self.network,
+                Module::Disk => self.disk,
+                Module::Processes => self.processes,
+            }
+        }
+
+        pub fn toggle(&mut self, module: Module) {
+            match module {
+                Module::Battery => self.battery = !self.battery,
+                Module::Network => self.network = !self.network,
+                Module::Disk => self.disk = !self.disk,
+                Module::Processes => self.processes = !self.processes,
+            }
+        }
+    }
+
+    impl Default for EnabledMetrics {
+        fn default() -> Self {
+            Self {
+                battery: true,
+                network: true,
+                disk: true,
+                processes: true,
+            }
+        }
+    }
+
+    impl Config {
+        /// Intervalle réellement utilisé : sysinfo a besoin d'un délai minimal entre
+        /// deux lectures pour que l'usage CPU ait un sens.
+        pub fn effective_refresh_ms(&self) -> u64 {
+            self.refresh_ms
+                .max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.as_millis() as u64)
+        }
+
+        pub fn path() -> Option<PathBuf> {
+            dirs::config_dir().map(|dir| dir.join("monitor_app").join("config.toml"))
+        }
+
+        /// Charge la configuration, ou les valeurs par défaut si le fichier est absent ou invalide.
+        pub fn load() -> Self {
+            Self::path()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .and_then(|content| toml::from_str(&content).ok())
+                .unwrap_or_default()
+        }
+
+        pub fn save(&self) -> std::io::Result<()> {
+            let path = Self::path().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::NotFound, "dossier de configuration introuvable")
+            })?;
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let content = toml::to_string_pretty(self).map_err(std::io::Error::other)?;
+            std::fs::write(path, content)
+        }
+    }
+}
+
+pub use config::{
+    AccentSource, BaselineMode, Config, CpuSource, DiskScope, EnabledMetrics, FillStyle, Icon, IconSet, Module,
+    NetDisplay, PointAggregation, Smoothing, SustainedAlert, Thresholds, TrayColorSource,
+};
+
+// ============================================================================
+// PLACEMENT DE FENÊTRE
+// ============================================================================
+pub mod placement {
+    use iced::{Point, Rectangle, Size};
+
+    // Décalage par rapport au bord droit et au haut de l'écran (sous la barre de menu)
+    const RIGHT_MARGIN: f32 = 10.0;
+    const TOP_MARGIN: f32 = 30.0;
+
+    /// Garde `pos` si un moniteur la contient, sinon place la fenêtre en haut à droite
+    /// du moniteur principal (le premier de la liste).
+    pub fn clamp_to_monitors(pos: Point, size: Size, monitors: &[Rectangle]) -> Point {
+        if monitors.is_empty() || monitors.iter().any(|monitor| monitor.contains(pos)) {
+            return pos;
+        }
+
+        top_right(monitors[0], size)
+    }
+
+    /// Colle la fenêtre au bord (ou au coin) du moniteur dont elle est à moins
+    /// de `threshold` pixels.
+    pub fn snap_to_edges(pos: Point, size: Size, monitor: Rectangle, threshold: f32) -> Point {
+        let mut snapped = pos;
+        if (pos.x - monitor.x).abs() <= threshold {
+            snapped.x = monitor.x;
+        } else if (monitor.x + monitor.width - (pos.x + size.width)).abs() <= threshold {
+            snapped.x = monitor.x + monitor.width - size.width;
+        }
+
+        if (pos.y - monitor.y).abs() <= threshold {
+            snapped.y = monitor.y;
+        } else if (monitor.y + monitor.height - (pos.y + size.height)).abs() <= threshold {
+            snapped.y = monitor.y + monitor.height - size.height;
+        }
+
+        snapped
+    }
+
+    /// Moniteur contenant `point`, parmi ceux de `monitors`.
+    pub fn monitor_at(point: Point, monitors: &[Rectangle]) -> Option<Rectangle> {
+        monitors.iter().copied().find(|monitor| monitor.contains(point))
+    }
+
+    /// Rectangles des moniteurs branchés, en coordonnées logiques, le principal en
+    /// premier. iced ne donne que la taille du moniteur courant, sans sa position :
+    /// la géométrie vient du système. Vide si elle n'a pas pu être lue.
+    #[cfg(feature = "widget")]
+    pub fn monitors() -> Vec<Rectangle> {
+        let Ok(mut displays) = display_info::DisplayInfo::all() else {
+            return Vec::new();
+        };
+        displays.sort_by_key(|display| !display.is_primary);
+
+        displays
+            .iter()
+            .map(|display| {
+                // Coordonnées physiques sous Windows, déjà logiques ailleurs
+                let scale = if cfg!(windows) && display.scale_factor > 0.0 {
+                    display.scale_factor
+                } else {
+                    1.0
+                };
+                Rectangle::new(
+                    Point::new(display.x as f32 / scale, display.y as f32 / scale),
+                    Size::new(display.width as f32 / scale, display.height as f32 / scale),
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "widget"))]
+    pub fn monitors() -> Vec<Rectangle> {
+        Vec::new()
+    }
+
+    pub fn top_right(monitor: Rectangle, size: Size) -> Point {
+        Point::new(
+            (monitor.x + monitor.width - size.width - RIGHT_MARGIN).max(monitor.x),
+            monitor.y + TOP_MARGIN,
+        )
+    }
+}
+
+pub use placement::{clamp_to_monitors, monitor_at, monitors, snap_to_edges, top_right};
+
+// ============================================================================
+// COMPOSANTS D'INTERFACE
+// ============================================================================
+// Partagés par l'application complète et le widget, génériques sur leur type `Message`.
+pub mod ui {
+    use crate::{
+        normalize, smooth_with_gaps, BaselineMode, FillStyle, History, Smoothing, Thresholds,
+    };
+    use iced::widget::canvas::{
+        self, gradient, Cache, Frame, Geometry, LineDash, Path, Program, Stroke,
+    };
+    use iced::widget::{button, container, row, text};
+    use iced::{mouse, Border, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+    /// Courbe d'un historique, mise en cache jusqu'au prochain échantillon.
+    ///
+    /// Construite par [`Sparkline::new`] ; le style optionnel s'ajoute par méthodes
+    /// (`Sparkline::new(&history, 100.0, &cache).unit("%").guides()`).
+    pub struct Sparkline<'a> {
+        data: &'a History,
+        color: Color,
+        max_value: f32,
+        baseline: BaselineMode,
+        fill: FillStyle,
+        smoothing: Smoothing,
+        // Fond et bordure (couleur, épaisseur) du graphique ; transparent si `None`
+        background: Option<Color>,
+        border: Option<(Color, f32)>,
+        // Unité de la dernière valeur, affichée en haut à droite (« 73 % ») ; rien si `None`
+        unit: Option<&'static str>,
+        // Lignes pointillées au maximum et à la moyenne de l'historique, pour lire
+        // l'échelle des graphiques qui suivent leur propre maximum
+        show_guides: bool,
+        cache: &'a Cache,
+    }
+
+    impl<'a> Sparkline<'a> {
+        /// Courbe blanche sans remplissage ni lissage, base à zéro, sur fond transparent.
+        pub fn new(data: &'a History, max_value: f32, cache: &'a Cache) -> Self {
+            Self {
+                data,
+                color: Color::WHITE,
+                max_value,
+                baseline: BaselineMode::default(),
+                fill: FillStyle::default(),
+                smoothing: Smoothing::default(),
+                background: None,
+                border: None,
+                unit: None,
+                show_guides: false,
+                cache,
+            }
+        }
+
+        pub fn color(mut self, color: Color) -> Self {
+            self.color = color;
+            self
+        }
+
+        pub fn baseline(mut self, baseline: BaselineMode) -> Self {
+            self.baseline = baseline;
+            self
+        }
+
+        pub fn fill(mut self, fill: FillStyle) -> Self {
+            self.fill = fill;
+            self
+        }
+
+        pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+            self.smoothing = smoothing;
+            self
+        }
+
+        pub fn background(mut self, background: Color) -> Self {
+            self.background = Some(background);
+            self
+        }
+
+        pub fn border(mut self, color: Color, width: f32) -> Self {
+            self.border = Some((color, width));
+            self
+        }
+
+        pub fn unit(mut self, unit: &'static str) -> Self {
+            self.unit = Some(unit);
+            self
+        }
+
+        pub fn guides(mut self) -> Self {
+            self.show_guides = true;
+            self
+        }
+    }
+
+    impl Sparkline<'_> {
+        const STROKE_WIDTH: f32 = 2.0;
+        // Opacité du remplissage uni, et du haut du dégradé
+        const FILL_ALPHA: f32 = 0.3;
+        const CORNER_RADIUS: f32 = 6.0;
+        const LABEL_SIZE: f32 = 12.0;
+        const LABEL_MARGIN: f32 = 4.0;
+        const GUIDE_DASH: [f32; 2] = [4.0, 3.0];
+        const GUIDE_LABEL_SIZE: f32 = 10.0;
+
+        /// Bornes verticales du tracé. Un `max_value` inexploitable (NaN, infini, nul :
+        /// maximum calculé sur un historique brut) est remplacé par celui des valeurs.
+        fn value_range(&self, values: &[f32]) -> (f32, f32) {
+            let max_value = if self.max_value.is_finite() && self.max_value > 0.0 {
+                self.max_value
+            } else {
+                values.iter().copied().fold(1.0, f32::max)
+            };
+            let min_value = match self.baseline {
+                BaselineMode::Zero => 0.0,
+                BaselineMode::Min => values
+                    .iter()
+                    .copied()
+                    .fold(f32::INFINITY, f32::min)
+                    .clamp(0.0, max_value),
+            };
+            (min_value, max_value)
+        }
+
+        // Le frame est en coordonnées logiques : le renderer applique lui-même le
+        // facteur d'échelle (Retina, 4K) au maillage, qui reste donc net. Le tracé
+        // est en revanche décalé d'une demi-épaisseur de trait pour que les valeurs
+        // extrêmes ne soient pas rognées par les bords du canvas.
+        fn y_of(value: f32, (min_value, max_value): (f32, f32), height: f32) -> f32 {
+            let inset = Self::STROKE_WIDTH / 2.0;
+            let plot_height = (height - Self::STROKE_WIDTH).max(0.0);
+            let range = max_value - min_value;
+            let ratio = if range > 0.0 {
+                (value.clamp(min_value, max_value) - min_value) / range
+            } else {
+                0.0
+            };
+            inset + plot_height - (ratio * plot_height)
+        }
+
+        fn segments(&self, values: &[f32], range: (f32, f32), size: Size) -> Vec<Vec<Point>> {
+            let step_x = if values.len() > 1 {
+                size.width / (values.len() as f32 - 1.0)
+            } else {
+                size.width
+            };
+
+            gap_segments(values, |i, value| {
+                Point::new(i as f32 * step_x, Self::y_of(value, range, size.height))
+            })
+        }
+
+        // Dernier point tracé (`values`, donc lissé comme la courbe), une décimale sous 10
+        fn value_label(&self, values: &[f32]) -> Option<String> {
+            let (Some(unit), Some(&value)) = (self.unit, values.last()) else {
+                return None;
+            };
+            if !value.is_finite() {
+                return None;
+            }
+
+            let value = if value.abs() < 10.0 {
+                format!("{:.1}", value)
+            } else {
+                format!("{:.0}", value)
+            };
+            Some(format!("{} {}", value, unit).trim_end().to_string())
+        }
+
+        fn draw_value(&self, frame: &mut Frame, bounds: Rectangle, values: &[f32]) {
+            let Some(content) = self.value_label(values) else {
+                return;
+            };
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(bounds.width - Self::LABEL_MARGIN, Self::LABEL_MARGIN),
+                color: self.color,
+                size: Self::LABEL_SIZE.into(),
+                align_x: iced::widget::text::Alignment::Right,
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    impl<Message> Program<Message> for Sparkline<'_> {
+        type State = ();
+
+        fn draw(
+            &self,
+            _state: &Self::State,
+            renderer: &Renderer,
+            _theme: &Theme,
+            bounds: Rectangle,
+            _cursor: mouse::Cursor,
+        ) -> Vec<Geometry> {
+            let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+                // Le cadre est dessiné même sans données, pour garder la zone visible
+                if let Some(background) = self.background {
+                    frame.fill(
+                        &Path::rounded_rectangle(
+                            Point::ORIGIN,
+                            bounds.size(),
+                            Self::CORNER_RADIUS.into(),
+                        ),
+                        background,
+                    );
+                }
+                if let Some((color, width)) = self.border {
+                    // Décalé d'une demi-épaisseur pour que le trait ne soit pas rogné
+                    let inset = width / 2.0;
+                    frame.stroke(
+                        &Path::rounded_rectangle(
+                            Point::new(inset, inset),
+                            Size::new(bounds.width - width, bounds.height - width),
+                            (Self::CORNER_RADIUS - inset).max(0.0).into(),
+                        ),
+                        Stroke::default().with_width(width).with_color(color),
+                    );
+                }
+
+                // Les valeurs non finies (NaN…) interrompent la courbe au lieu de la casser
+                let values = smooth_with_gaps(self.data, self.smoothing);
+                if self.data.len() < 2 {
+                    self.draw_value(frame, bounds, &values);
+                    return;
+                }
+
+                let range = self.value_range(&values);
+                let y_of = |value: f32| Self::y_of(value, range, bounds.height);
+                let segments = self.segments(&values, range, bounds.size());
+                let path = polyline(&segments);
+
+                // Aire sous chaque tronçon de courbe, refermée le long du bas du graphique
+                if self.fill != FillStyle::None {
+                    let area = Path::new(|builder| {
+                        for points in &segments {
+                            builder.move_to(Point::new(points[0].x, bounds.height));
+                            for &point in points {
+                                builder.line_to(point);
+                            }
+                            builder.line_to(Point::new(points[points.len() - 1].x, bounds.height));
+                            builder.close();
+                        }
+                    });
+
+                    let top = Color { a: Self::FILL_ALPHA, ..self.color };
+                    match self.fill {
+                        FillStyle::Solid => frame.fill(&area, top),
+                        FillStyle::Gradient => frame.fill(
+                            &area,
+                            gradient::Linear::new(Point::ORIGIN, Point::new(0.0, bounds.height))
+                                .add_stop(0.0, top)
+                                .add_stop(1.0, Color { a: 0.0, ..self.color }),
+                        ),
+                        FillStyle::None => {}
+                    }
+                }
+
+                // Repères sur les mesures brutes : le pic réel, même si le lissage l'écrête
+                if self.show_guides {
+                    let samples: Vec<f32> =
+                        self.data.iter().copied().filter(|value| value.is_finite()).collect();
+                    let peak = samples.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                    let mean = samples.iter().sum::<f32>() / samples.len().max(1) as f32;
+                    let guide_color = Color { a: 0.6, ..self.color };
+
+                    for (label, value) in [("max", peak), ("moy", mean)] {
+                        if !value.is_finite() {
+                            continue;
+                        }
+                        let y = y_of(value);
+                        frame.stroke(
+                            &Path::line(Point::new(0.0, y), Point::new(bounds.width, y)),
+                            Stroke {
+                                line_dash: LineDash {
+                                    segments: &Self::GUIDE_DASH,
+                                    offset: 0,
+                                },
+                                ..Stroke::default().with_width(1.0).with_color(guide_color)
+                            },
+                        );
+                        // Étiquette au-dessus de la ligne, ou dessous si elle touche le haut
+                        let (label_y, align_y) = if y > Self::GUIDE_LABEL_SIZE + 2.0 {
+                            (y - 1.0, iced::alignment::Vertical::Bottom)
+                        } else {
+                            (y + 1.0, iced::alignment::Vertical::Top)
+                        };
+                        frame.fill_text(canvas::Text {
+                            content: format!("{} {:.1}", label, value),
+                            position: Point::new(Self::LABEL_MARGIN, label_y),
+                            color: guide_color,
+                            size: Self::GUIDE_LABEL_SIZE.into(),
+                            align_y,
+                            ..canvas::Text::default()
+                        });
+                    }
+                }
+
+                frame.stroke(
+                    &path,
+                    Stroke::default()
+                        .with_width(Self::STROKE_WIDTH)
+                        .with_color(self.color),
+                );
+
+                self.draw_value(frame, bounds, &values);
+            });
+
+            vec![geometry]
+        }
+    }
+
+    /// Deux historiques superposés, chacun ramené à son propre maximum : les unités
+    /// diffèrent (%, Mbps…), seule la coïncidence des pics compte.
+    pub struct CorrelationChart<'a> {
+        pub first: &'a History,
+        pub second: &'a History,
+        pub first_color: Color,
+        pub second_color: Color,
+        pub smoothing: Smoothing,
+        pub cache: &'a Cache,
+    }
+
+    impl CorrelationChart<'_> {
+        const STROKE_WIDTH: f32 = 2.0;
+    }
+
+    impl<Message> Program<Message> for CorrelationChart<'_> {
+        type State = ();
+
+        fn draw(
+            &self,
+            _state: &Self::State,
+            renderer: &Renderer,
+            _theme: &Theme,
+            bounds: Rectangle,
+            _cursor: mouse::Cursor,
+        ) -> Vec<Geometry> {
+            let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+                let samples = self.first.len().max(self.second.len());
+                if samples < 2 {
+                    return;
+                }
+
+                let inset = Self::STROKE_WIDTH / 2.0;
+                let plot_height = (bounds.height - Self::STROKE_WIDTH).max(0.0);
+                let step_x = bounds.width / (samples as f32 - 1.0);
+
+                for (data, color) in [
+                    (self.first, self.first_color),
+                    (self.second, self.second_color),
+                ] {
+                    if data.len() < 2 {
+                        continue;
+                    }
+
+                    let values = normalize(&smooth_with_gaps(data, self.smoothing));
+                    // Une série plus courte est alignée à droite, sur les échantillons récents
+                    let offset = samples - values.len();
+                    let segments = gap_segments(&values, |i, ratio| {
+                        Point::new(
+                            (offset + i) as f32 * step_x,
+                            inset + plot_height - (ratio * plot_height),
+                        )
+                    });
+
+                    frame.stroke(
+                        &polyline(&segments),
+                        Stroke::default()
+                            .with_width(Self::STROKE_WIDTH)
+                            .with_color(color),
+                    );
+                }
+            });
+
+            vec![geometry]
+        }
+    }
+
+    // Tronçons de points consécutifs aux valeurs finies : une valeur non finie (trou de
+    // l'historique repris, débit sur une durée nulle) coupe la courbe
+    fn gap_segments(values: &[f32], point: impl Fn(usize, f32) -> Point) -> Vec<Vec<Point>> {
+        let mut segments = vec![Vec::new()];
+        for (i, &value) in values.iter().enumerate() {
+            match segments.last_mut() {
+                Some(segment) if value.is_finite() => segment.push(point(i, value)),
+                Some(segment) if !segment.is_empty() => segments.push(Vec::new()),
+                _ => {}
+            }
+        }
+        segments.retain(|segment| !segment.is_empty());
+        segments
+    }
+
+    // Une ligne brisée par tronçon, sans relier les tronçons entre eux
+    fn polyline(segments: &[Vec<Point>]) -> Path {
+        Path::new(|builder| {
+            for points in segments {
+                builder.move_to(points[0]);
+                for &point in &points[1..] {
+                    builder.line_to(point);
+                }
+            }
+        })
+    }
+
+    /// Unité d'affichage d'un débit, choisie selon son ordre de grandeur.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum RateUnit {
+        Kbps,
+        Mbps,
+        Gbps,
+    }
+
+    impl RateUnit {
+        pub fn for_value(mbps: f32) -> Self {
+            if mbps >= 1_000.0 {
+                RateUnit::Gbps
+            } else if mbps >= 1.0 {
+                RateUnit::Mbps
+            } else {
+                RateUnit::Kbps
+            }
+        }
+
+        pub fn format(self, mbps: f32) -> String {
+            match self {
+                RateUnit::Kbps => format!("{:.0} Kbps", mbps * 1_000.0),
+                RateUnit::Mbps => format!("{:.2} Mbps", mbps),
+                RateUnit::Gbps => format!("{:.2} Gbps", mbps / 1_000.0),
+            }
+        }
+    }
+
+    /// Débit (donné en Mbps) dans l'unité la plus lisible.
+    pub fn format_rate(mbps: f32) -> String {
+        RateUnit::for_value(mbps).format(mbps)
+    }
+
+    /// Intervalle de rafraîchissement : « 2 s », ou « 500 ms » hors secondes entières.
+    pub fn format_interval(refresh_ms: u64) -> String {
+        if refresh_ms >= 1_000 && refresh_ms.is_multiple_of(1_000) {
+            format!("{} s", refresh_ms / 1_000)
+        } else {
+            format!("{} ms", refresh_ms)
+        }
+    }
+
+    /// Durée restante de la batterie : « 3 h 45 », ou « 45 min » sous l'heure.
+    pub fn format_eta(remaining: std::time::Duration) -> String {
+        let minutes = remaining.as_secs() / 60;
+        if minutes >= 60 {
+            format!("{} h {:02}", minutes / 60, minutes % 60)
+        } else {
+            format!("{} min", minutes)
+        }
+    }
+
+    /// Durée longue : « 3 j 4 h 12 min », sans les unités nulles de tête, et en
+    /// secondes sous la minute.
+    pub fn format_duration(secs: u64) -> String {
+        let (days, hours, minutes) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60);
+        if days > 0 {
+            format!("{} j {} h {} min", days, hours, minutes)
+        } else if hours > 0 {
+            format!("{} h {} min", hours, minutes)
+        } else if minutes > 0 {
+            format!("{} min", minutes)
+        } else {
+            format!("{} s", secs)
+        }
+    }
+
+    /// Taille donnée en Go, affichée en Mo sous le gigaoctet plutôt qu'en « 0 Go ».
+    pub fn format_gb(gb: f32) -> String {
+        if gb >= 1.0 {
+            format!("{:.0} Go", gb)
+        } else {
+            format!("{:.0} Mo", gb * 1024.0)
+        }
+    }
+
+    /// Volume en Mo, ou en Go au-delà d'un gigaoctet.
+    pub fn format_bytes(bytes: u64) -> String {
+        if bytes >= 1_073_741_824 {
+            format!("{:.2} Go", bytes as f32 / 1_073_741_824.0)
+        } else {
+            format!("{:.1} Mo", bytes as f32 / 1_048_576.0)
+        }
+    }
+
+    const MIN_WHITE_CONTRAST: f32 = 3.0;
+
+    /// Texte lisible sur `bg` : le blanc habituel tant que son rapport de contraste
+    /// WCAG atteint 3:1, sinon le noir (fonds clairs comme l'orange ou le jaune).
+    pub fn contrast_color(bg: Color) -> Color {
+        let linear = |channel: f32| {
+            if channel <= 0.03928 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let luminance = 0.2126 * linear(bg.r) + 0.7152 * linear(bg.g) + 0.0722 * linear(bg.b);
+
+        if 1.05 / (luminance + 0.05) >= MIN_WHITE_CONTRAST {
+            Color::WHITE
+        } else {
+            Color::BLACK
+        }
+    }
+
+    /// Vert sous `warn`, orange jusqu'à `crit`, rouge au-delà (couleurs de `LoadLevel`).
+    pub fn threshold_color(value: f32, warn: f32, crit: f32) -> Color {
+        let [r, g, b] = Thresholds { warn, crit }.level(value).rgb();
+        Color::from_rgb8(r, g, b)
+    }
+
+    /// Ligne « libellé … valeur » sur fond coloré.
+    pub fn create_metric_row<Message: 'static>(
+        label: String,
+        value: String,
+        color: Color,
+    ) -> Element<'static, Message> {
+        let text_color = contrast_color(color);
+
+        container(
+            row![
+                text(label)
+                    .size(13)
+                    .color(text_color)
+                    .width(Length::Fill),
+                text(value)
+                    .size(16)
+                    .color(text_color)
+            ]
+            .align_y(iced::Alignment::Center)
+            .spacing(10)
+            .padding(8)
+        )
+        .style(move |_theme: &Theme| {
+            container::Style {
+                background: Some(color.into()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        })
+        .width(Length::Fill)
+        .into()
+    }
+
+    // Gabarit des onglets : celui de l'application complète, ou celui du widget
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum TabSize {
+        Regular,
+        Compact,
+    }
+
+    impl TabSize {
+        fn text_size(self) -> f32 {
+            match self {
+                TabSize::Regular => 18.0,
+                TabSize::Compact => 12.0,
+            }
+        }
+
+        fn padding(self) -> [u16; 2] {
+            match self {
+                TabSize::Regular => [12, 24],
+                TabSize::Compact => [6, 12],
+            }
+        }
+
+        fn radius(self) -> f32 {
+            match self {
+                TabSize::Regular => 10.0,
+                TabSize::Compact => 6.0,
+            }
+        }
+    }
+
+    pub fn create_tab_button<Tab: PartialEq, Message: Clone + 'static>(
+        label: impl Into<String>,
+        tab: Tab,
+        current_tab: Tab,
+        accent: Color,
+        size: TabSize,
+        on_select: impl Fn(Tab) -> Message,
+    ) -> Element<'static, Message> {
+        create_themed_tab_button(label, tab, current_tab, accent, size, false, on_select)
+    }
+
+    /// Variante de `create_tab_button` dont les onglets inactifs suivent un thème sombre.
+    pub fn create_themed_tab_button<Tab: PartialEq, Message: Clone + 'static>(
+        label: impl Into<String>,
+        tab: Tab,
+        current_tab: Tab,
+        accent: Color,
+        size: TabSize,
+        dark: bool,
+        on_select: impl Fn(Tab) -> Message,
+    ) -> Element<'static, Message> {
+        let is_active = tab == current_tab;
+        let active_text = contrast_color(accent);
+        let (inactive_background, inactive_text) = if dark {
+            (Color::from_rgb8(0x37, 0x41, 0x51), Color::from_rgb8(0x9c, 0xa3, 0xaf))
+        } else {
+            (Color::from_rgb8(0xe5, 0xe7, 0xeb), Color::from_rgb8(0x6b, 0x7c, 0x93))
+        };
+
+        button(
+            text(label.into())
+                .size(size.text_size())
+                .color(if is_active { active_text } else { inactive_text })
+        )
+        .padding(size.padding())
+        .style(move |_theme: &Theme, _status| {
+            button::Style {
+                background: Some(if is_active { accent } else { inactive_background }.into()),
+                border: Border {
+                    radius: size.radius().into(),
+                    ..Default::default()
+                },
+                text_color: if is_active { active_text } else { inactive_text },
+                ..Default::default()
+            }
+        })
+        .on_press(on_select(tab))
+        .into()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn sparkline_geometry_stays_finite_with_nan_and_infinity() {
+            let mut data = History::new(8);
+            for value in [1.0, f32::NAN, f32::INFINITY, 4.0, f32::NEG_INFINITY, 2.0] {
+                data.push(value);
+            }
+            let cache = Cache::new();
+            let size = Size::new(120.0, 40.0);
+
+            for max_value in [f32::NAN, f32::INFINITY, 0.0, 5.0] {
+                for baseline in [BaselineMode::Zero, BaselineMode::Min] {
+                    let sparkline = Sparkline::new(&data, max_value, &cache).baseline(baseline);
+                    let values = smooth_with_gaps(&data, Smoothing::None);
+                    let range = sparkline.value_range(&values);
+                    assert!(range.0.is_finite() && range.1.is_finite() && range.1 > 0.0);
+
+                    // Les valeurs non finies sont des trous : seuls 1, 4 et 2 sont tracés
+                    let points: Vec<Point> =
+                        sparkline.segments(&values, range, size).into_iter().flatten().collect();
+                    assert_eq!(points.len(), 3);
+                    for point in points {
+                        assert!((0.0..=size.width).contains(&point.x), "{:?}", point);
+                        assert!((0.0..=size.height).contains(&point.y), "{:?}", point);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn restored_gap_breaks_the_sparkline() {
+            let mut data = History::new(8);
+            for value in [10.0, 20.0, f32::NAN, f32::NAN, 30.0, 40.0] {
+                data.push(value);
+            }
+            let cache = Cache::new();
+            let sparkline = Sparkline::new(&data, 100.0, &cache).smoothing(Smoothing::MovingAverage(2));
+            let size = Size::new(100.0, 40.0);
+
+            let values = smooth_with_gaps(&data, Smoothing::MovingAverage(2));
+            let segments = sparkline.segments(&values, sparkline.value_range(&values), size);
+            assert_eq!(segments.len(), 2);
+            assert_eq!(segments[0].len(), 2);
+            assert_eq!(segments[1].len(), 2);
+            // Le second tronçon reprend à l'abscisse du premier échantillon après le trou
+            assert_eq!(segments[1][0].x, 80.0);
+            assert!(segments[0][1].x < segments[1][0].x);
+        }
+
+        #[test]
+        fn value_label_matches_the_smoothed_line() {
+            let mut data = History::new(4);
+            for value in [10.0, 20.0, 30.0, 60.0] {
+                data.push(value);
+            }
+            let cache = Cache::new();
+            let sparkline = Sparkline::new(&data, 100.0, &cache)
+                .smoothing(Smoothing::MovingAverage(2))
+                .unit("%");
+
+            let values = smooth_with_gaps(&data, Smoothing::MovingAverage(2));
+            assert_eq!(sparkline.value_label(&values).as_deref(), Some("45 %"));
+            assert_eq!(sparkline.value_label(&[]), None);
+        }
+
+        #[test]
+        fn contrast_color_picks_readable_text() {
+            assert_eq!(contrast_color(Color::BLACK), Color::WHITE);
+            assert_eq!(contrast_color(Color::WHITE), Color::BLACK);
+            assert_eq!(contrast_color(Color::from_rgb(0.9, 0.2, 0.2)), Color::WHITE);
+            assert_eq!(contrast_color(Color::from_rgb(0.2, 0.4, 0.8)), Color::WHITE);
+            // Orange et jaune : le blanc n'atteint pas 3:1
+            assert_eq!(contrast_color(Color::from_rgb(1.0, 0.6, 0.0)), Color::BLACK);
+            assert_eq!(contrast_color(Color::from_rgb(1.0, 0.9, 0.1)), Color::BLACK);
+        }
+
+        #[test]
+        fn threshold_color_follows_load_levels() {
+            let (green, orange, red) = (
+                Color::from_rgb8(0x10, 0xb9, 0x81),
+                Color::from_rgb8(0xf5, 0x9e, 0x0b),
+                Color::from_rgb8(0xef, 0x44, 0x44),
+            );
+            assert_eq!(threshold_color(49.9, 50.0, 80.0), green);
+            assert_eq!(threshold_color(50.0, 50.0, 80.0), orange);
+            assert_eq!(threshold_color(80.0, 50.0, 80.0), red);
+        }
+
+        #[test]
+        fn contrast_color_switches_around_luminance_threshold() {
+            // Le blanc atteint 3:1 jusqu'à une luminance de 0,3 (gris ≈ 0,584)
+            assert_eq!(contrast_color(Color::from_rgb(0.57, 0.57, 0.57)), Color::WHITE);
+            assert_eq!(contrast_color(Color::from_rgb(0.60, 0.60, 0.60)), Color::BLACK);
+        }
+
+        #[test]
+        fn format_duration_picks_largest_units() {
+            assert_eq!(format_duration(0), "0 s");
+            assert_eq!(format_duration(59), "59 s");
+            assert_eq!(format_duration(60), "1 min");
+            assert_eq!(format_duration(3_600), "1 h 0 min");
+            assert_eq!(format_duration(3 * 86_400 + 4 * 3_600 + 12 * 60 + 30), "3 j 4 h 12 min");
+        }
+
+        #[test]
+        fn small_volume_is_shown_in_megabytes() {
+            let total = 512 * 1_048_576;
+            let usage = crate::DiskUsage::from_bytes(total / 4, total);
+            assert_eq!(format_gb(usage.total_gb), "512 Mo");
+            assert_eq!(format_gb(usage.used_gb), "128 Mo");
+            assert!(usage.percent > 0.0);
+            assert_eq!(format_gb(250.4), "250 Go");
+        }
+    }
+}
+
+pub use ui::{
+    contrast_color, create_metric_row, create_tab_button, create_themed_tab_button, format_bytes,
+    format_duration, format_eta, format_gb, format_interval, format_rate, threshold_color,
+    CorrelationChart, RateUnit, Sparkline, TabSize,
+};
+
+// ============================================================================
+// STATISTIQUES
+// ============================================================================
+pub mod stats {
+    use crate::{PointAggregation, Smoothing};
+    use serde::{Deserialize, Serialize};
+    use std::collections::{vec_deque, BTreeMap, VecDeque};
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// Écart-type (population) des échantillons finis, 0 avec moins de 2 valeurs.
+    /// Calculé en une passe (algorithme de Welford).
+    pub fn std_dev<'a>(data: impl IntoIterator<Item = &'a f32>) -> f32 {
+        let mut count = 0.0_f32;
+        let mut mean = 0.0_f32;
+        let mut sum_squares = 0.0_f32;
+
+        for &value in data.into_iter().filter(|value| value.is_finite()) {
+            count += 1.0;
+            let delta = value - mean;
+            mean += delta / count;
+            sum_squares += delta * (value - mean);
+        }
+
+        if count < 2.0 {
+            return 0.0;
+        }
+
+        (sum_squares / count).sqrt()
+    }
+
+    /// Mesures reçues depuis le dernier point d'un graphique.
+    #[derive(Debug, Clone, Default)]
+    pub struct PointBuffer {
+        samples: Vec<f32>,
+    }
+
+    impl PointBuffer {
+        pub fn add(&mut self, value: f32) {
+            self.samples.push(value);
+        }
+
+        /// Agrège les mesures en un point et vide le tampon. `None` sans mesure.
+        pub fn flush(&mut self, aggregation: PointAggregation) -> Option<f32> {
+            let last = *self.samples.last()?;
+            let point = match aggregation {
+                PointAggregation::Last => last,
+                PointAggregation::Min => self.samples.iter().copied().fold(f32::INFINITY, f32::min),
+                PointAggregation::Max => {
+                    self.samples.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+                }
+                PointAggregation::Avg => {
+                    self.samples.iter().sum::<f32>() / self.samples.len() as f32
+                }
+            };
+            self.samples.clear();
+            Some(point)
+        }
+
+        pub fn clear(&mut self) {
+            self.samples.clear();
+        }
+    }
+
+    /// Moyenne glissante sur les `window` derniers points. Au début de l'historique,
+    /// la fenêtre se limite aux points disponibles.
+    pub fn moving_average<'a>(data: impl IntoIterator<Item = &'a f32>, window: usize) -> Vec<f32> {
+        let samples: Vec<f32> = data.into_iter().copied().collect();
+        let window = window.max(1);
+        let mut sum = 0.0;
+
+        samples
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                sum += value;
+                if i >= window {
+                    sum -= samples[i - window];
+                }
+                sum / (i + 1).min(window) as f32
+            })
+            .collect()
+    }
+
+    /// Série affichée pour `raw` selon le lissage choisi. La dernière valeur est
+    /// celle à afficher à côté de la courbe, pour que les deux concordent.
+    pub fn apply_smoothing(raw: &[f32], mode: Smoothing) -> Vec<f32> {
+        match mode {
+            Smoothing::None => raw.to_vec(),
+            Smoothing::MovingAverage(window) => moving_average(raw, window),
+            Smoothing::Exponential(alpha) => {
+                let alpha = if alpha.is_finite() { alpha.clamp(0.01, 1.0) } else { 1.0 };
+                let mut smoothed = None;
+                raw.iter()
+                    .map(|&value| {
+                        let next =
+                            smoothed.map_or(value, |previous: f32| previous + alpha * (value - previous));
+                        smoothed = Some(next);
+                        next
+                    })
+                    .collect()
+            }
+            Smoothing::PercentileClamp(percentile) => {
+                let mut sorted = raw.to_vec();
+                sorted.sort_by(f32::total_cmp);
+                let Some(last) = sorted.len().checked_sub(1) else {
+                    return Vec::new();
+                };
+                let rank = (percentile.clamp(0.0, 100.0) / 100.0 * last as f32).round() as usize;
+                let ceiling = sorted[rank];
+                raw.iter().map(|&value| value.min(ceiling)).collect()
+            }
+        }
+    }
+
+    /// Comme `apply_smoothing`, appliqué séparément à chaque suite de valeurs finies :
+    /// les valeurs non finies (trou laissé par l'arrêt de l'application, débit sur une
+    /// durée nulle) restent des NaN et ne se mêlent pas au lissage de leurs voisines.
+    pub fn smooth_with_gaps<'a>(data: impl IntoIterator<Item = &'a f32>, mode: Smoothing) -> Vec<f32> {
+        let raw: Vec<f32> = data.into_iter().copied().collect();
+        let mut smoothed = Vec::with_capacity(raw.len());
+        for run in raw.split_inclusive(|value| !value.is_finite()) {
+            let (finite, gap) = match run.split_last() {
+                Some((last, finite)) if !last.is_finite() => (finite, true),
+                _ => (run, false),
+            };
+            smoothed.extend(apply_smoothing(finite, mode));
+            if gap {
+                smoothed.push(f32::NAN);
+            }
+        }
+        smoothed
+    }
+
+    /// Dernier point de l'historique lissé, tel que tracé par sa courbe ; `None` s'il est
+    /// vide ou s'il se termine par un trou.
+    pub fn smoothed_latest(data: &History, mode: Smoothing) -> Option<f32> {
+        smooth_with_gaps(data, mode).last().copied().filter(|value| value.is_finite())
+    }
+
+    /// Remplace les valeurs non finies (NaN, ±∞) par la dernière valeur finie, ou 0.
+    pub fn finite_samples<'a>(data: impl IntoIterator<Item = &'a f32>) -> Vec<f32> {
+        let mut previous = 0.0;
+        data.into_iter()
+            .map(|&value| {
+                if value.is_finite() {
+                    previous = value;
+                }
+                previous
+            })
+            .collect()
+    }
+
+    /// Valeurs ramenées à [0, 1] par leur propre maximum (nulles si ce maximum ne l'est pas).
+    /// Les valeurs non finies restent des NaN, pour que les trous de la courbe subsistent.
+    pub fn normalize(values: &[f32]) -> Vec<f32> {
+        let max = values.iter().copied().fold(0.0_f32, f32::max);
+        values
+            .iter()
+            .map(|&value| match value {
+                value if !value.is_finite() => f32::NAN,
+                value if max > 0.0 => value.max(0.0) / max,
+                _ => 0.0,
+            })
+            .collect()
+    }
+
+    /// Certains conteneurs annoncent une mémoire totale nulle : aucune valeur RAM
+    /// n'est alors affichée plutôt qu'un 0 % trompeur.
+    pub fn memory_available(total_mem: u64) -> bool {
+        total_mem > 0
+    }
+
+    /// Pression mémoire en % : part de la RAM qui n'est plus disponible (cache
+    /// récupérable exclu, contrairement à la mémoire « utilisée »). `None` si la
+    /// mémoire totale n'est pas connue.
+    pub fn memory_pressure(available_mem: u64, total_mem: u64) -> Option<f32> {
+        memory_available(total_mem).then(|| {
+            (total_mem.saturating_sub(available_mem) as f32 / total_mem as f32) * 100.0
+        })
+    }
+
+    /// Nombre de cœurs dont l'utilisation dépasse `threshold` (%), 0 sans mesure par cœur.
+    pub fn busy_core_count(per_core: &[f32], threshold: f32) -> usize {
+        per_core.iter().filter(|&&usage| usage > threshold).count()
+    }
+
+    /// Moyenne et écart-type d'un historique : référence du fonctionnement normal.
+    pub fn learn_baseline<'a>(history: impl IntoIterator<Item = &'a f32>) -> (f32, f32) {
+        let samples: Vec<f32> =
+            history.into_iter().copied().filter(|value| value.is_finite()).collect();
+        if samples.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        (mean, std_dev(&samples))
+    }
+
+    // Alerte adaptative : une valeur est inhabituelle au-delà de moyenne + k·σ
+    // de la référence apprise, plutôt que d'un seuil fixe.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct BaselineComparator {
+        baseline: Option<(f32, f32)>,
+    }
+
+    impl BaselineComparator {
+        /// Apprend la référence sur l'historique, oubliée s'il a moins de 2 points.
+        pub fn learn(&mut self, history: &History) {
+            self.baseline = (history.len() >= 2).then(|| learn_baseline(history));
+        }
+
+        pub fn clear(&mut self) {
+            self.baseline = None;
+        }
+
+        pub fn baseline(&self) -> Option<(f32, f32)> {
+            self.baseline
+        }
+
+        pub fn exceeds(&self, value: f32, sigmas: f32) -> bool {
+            self.baseline
+                .is_some_and(|(mean, std_dev)| value > mean + sigmas * std_dev)
+        }
+    }
+
+    // Historique borné d'une métrique : l'échantillon le plus ancien est retiré
+    // en O(1) quand la capacité est atteinte.
+    #[derive(Debug, Clone, Default)]
+    pub struct History {
+        samples: VecDeque<f32>,
+        capacity: usize,
+    }
+
+    impl History {
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                samples: VecDeque::with_capacity(capacity),
+                capacity,
+            }
+        }
+
+        pub fn push(&mut self, value: f32) {
+            if self.samples.len() >= self.capacity {
+                self.samples.pop_front();
+            }
+            if self.capacity > 0 {
+                self.samples.push_back(value);
+            }
+        }
+
+        /// Change la capacité en ne gardant que les échantillons les plus récents.
+        pub fn set_capacity(&mut self, capacity: usize) {
+            self.capacity = capacity;
+            let extra = self.samples.len().saturating_sub(capacity);
+            self.samples.drain(..extra);
+        }
+
+        pub fn clear(&mut self) {
+            self.samples.clear();
+        }
+
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        pub fn len(&self) -> usize {
+            self.samples.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.samples.is_empty()
+        }
+
+        pub fn iter(&self) -> vec_deque::Iter<'_, f32> {
+            self.samples.iter()
+        }
+
+        pub fn get(&self, index: usize) -> Option<f32> {
+            self.samples.get(index).copied()
+        }
+    }
+
+    /// Exporte plusieurs historiques en CSV, une ligne par échantillon.
+    /// Le dernier échantillon est daté de `end`, les précédents en remontant de
+    /// `interval`. Les séries plus courtes sont complétées par des cellules vides
+    /// au début, leurs échantillons étant les plus récents.
+    pub fn history_csv(series: &[(&str, &History)], interval: Duration, end: SystemTime) -> String {
+        let rows = series.iter().map(|(_, history)| history.len()).max().unwrap_or(0);
+
+        let mut csv = String::from("timestamp");
+        for (name, _) in series {
+            csv.push(',');
+            csv.push_str(name);
+        }
+        csv.push('\n');
+
+        for row in 0..rows {
+            let age = interval.saturating_mul((rows - 1 - row) as u32);
+            let timestamp = end
+                .checked_sub(age)
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .unwrap_or_default();
+            csv.push_str(&format!("{:.3}", timestamp.as_secs_f64()));
+
+            for (_, history) in series {
+                csv.push(',');
+                let padding = rows - history.len();
+                // Un trou (NaN) laisse la cellule vide
+                if let Some(value) = row
+                    .checked_sub(padding)
+                    .and_then(|index| history.get(index))
+                    .filter(|value| value.is_finite())
+                {
+                    csv.push_str(&format!("{:.2}", value));
+                }
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Historiques et compteurs de session enregistrés à la fermeture, pour que
+    /// les graphiques reprennent au lancement suivant.
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct SavedHistory {
+        // Secondes écoulées depuis l'epoch Unix au moment de l'enregistrement
+        pub saved_at: u64,
+        // Écart entre deux échantillons (ms)
+        pub interval_ms: u64,
+        #[serde(with = "gaps_as_null")]
+        pub series: BTreeMap<String, Vec<f32>>,
+        pub counters: BTreeMap<String, f32>,
+    }
+
+    impl SavedHistory {
+        pub fn new(interval: Duration, now: SystemTime) -> Self {
+            Self {
+                saved_at: now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+                interval_ms: interval.as_millis() as u64,
+                ..Self::default()
+            }
+        }
+
+        pub fn path() -> Option<PathBuf> {
+            dirs::config_dir().map(|dir| dir.join("monitor_app").join("history.json"))
+        }
+
+        pub fn load() -> Option<Self> {
+            let content = std::fs::read_to_string(Self::path()?).ok()?;
+            serde_json::from_str(&content).ok()
+        }
+
+        pub fn save(&self) -> std::io::Result<()> {
+            let path = Self::path().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::NotFound, "dossier de configuration introuvable")
+            })?;
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            std::fs::write(path, serde_json::to_string(self)?)
+        }
+
+        /// Nombre d'échantillons qui auraient été collectés depuis l'enregistrement.
+        pub fn missed_samples(&self, now: SystemTime) -> u64 {
+            let now = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            now.saturating_sub(self.saved_at) * 1000 / self.interval_ms.max(1)
+        }
+
+        /// Vrai si une partie des données tient encore dans une fenêtre de `capacity` points.
+        pub fn is_fresh(&self, capacity: usize, now: SystemTime) -> bool {
+            self.missed_samples(now) < capacity as u64
+        }
+
+        /// Échantillons d'une série encore dans la fenêtre : le temps écoulé depuis
+        /// l'enregistrement repousse les plus anciens hors du graphique.
+        pub fn fresh_samples(&self, name: &str, capacity: usize, now: SystemTime) -> &[f32] {
+            let Some(samples) = self.series.get(name) else {
+                return &[];
+            };
+            let missed = usize::try_from(self.missed_samples(now)).unwrap_or(usize::MAX);
+            let keep = capacity.saturating_sub(missed).min(samples.len());
+            &samples[samples.len() - keep..]
+        }
+    }
+
+    // Les trous (NaN) d'une série sont enregistrés en `null`, que JSON sait représenter
+    mod gaps_as_null {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use std::collections::BTreeMap;
+
+        pub fn serialize<S: Serializer>(
+            series: &BTreeMap<String, Vec<f32>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            series
+                .iter()
+                .map(|(name, samples)| {
+                    let samples: Vec<Option<f32>> =
+                        samples.iter().map(|&value| value.is_finite().then_some(value)).collect();
+                    (name, samples)
+                })
+                .collect::<BTreeMap<_, _>>()
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<BTreeMap<String, Vec<f32>>, D::Error> {
+            let series = BTreeMap::<String, Vec<Option<f32>>>::deserialize(deserializer)?;
+            Ok(series
+                .into_iter()
+                .map(|(name, samples)| {
+                    (name, samples.into_iter().map(|value| value.unwrap_or(f32::NAN)).collect())
+                })
+                .collect())
+        }
+    }
+
+    impl<'a> IntoIterator for &'a History {
+        type Item = &'a f32;
+        type IntoIter = vec_deque::Iter<'a, f32>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.samples.iter()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn history_of(capacity: usize, values: &[f32]) -> History {
+            let mut history = History::new(capacity);
+            for &value in values {
+                history.push(value);
+            }
+            history
+        }
+
+        #[test]
+        fn history_drops_oldest_sample_when_full() {
+            let history = history_of(3, &[1.0, 2.0, 3.0, 4.0]);
+            assert_eq!(history.iter().copied().collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+        }
+
+        #[test]
+        fn shrinking_history_keeps_most_recent_samples() {
+            let mut history = history_of(5, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+            history.set_capacity(2);
+            assert_eq!(history.capacity(), 2);
+            assert_eq!(history.iter().copied().collect::<Vec<_>>(), [4.0, 5.0]);
+        }
+
+        #[test]
+        fn growing_history_keeps_samples_and_accepts_more() {
+            let mut history = history_of(2, &[1.0, 2.0]);
+            history.set_capacity(4);
+            history.push(3.0);
+            history.push(4.0);
+            history.push(5.0);
+            assert_eq!(history.iter().copied().collect::<Vec<_>>(), [2.0, 3.0, 4.0, 5.0]);
+        }
+
+        fn buffer_of(values: &[f32]) -> PointBuffer {
+            let mut buffer = PointBuffer::default();
+            for &value in values {
+                buffer.add(value);
+            }
+            buffer
+        }
+
+        #[test]
+        fn point_buffer_keeps_last_sample() {
+            let mut buffer = buffer_of(&[3.0, 9.0, 6.0]);
+            assert_eq!(buffer.flush(PointAggregation::Last), Some(6.0));
+        }
+
+        #[test]
+        fn point_buffer_keeps_minimum() {
+            let mut buffer = buffer_of(&[3.0, 9.0, 6.0]);
+            assert_eq!(buffer.flush(PointAggregation::Min), Some(3.0));
+        }
+
+        #[test]
+        fn point_buffer_averages_samples() {
+            let mut buffer = buffer_of(&[3.0, 9.0, 6.0]);
+            assert_eq!(buffer.flush(PointAggregation::Avg), Some(6.0));
+        }
+
+        #[test]
+        fn point_buffer_keeps_maximum() {
+            let mut buffer = buffer_of(&[3.0, 9.0, 6.0]);
+            assert_eq!(buffer.flush(PointAggregation::Max), Some(9.0));
+        }
+
+        #[test]
+        fn empty_point_buffer_yields_no_point() {
+            let mut buffer = buffer_of(&[3.0]);
+            assert_eq!(buffer.flush(PointAggregation::Avg), Some(3.0));
+            // Le tampon est vidé par `flush`
+            assert_eq!(buffer.flush(PointAggregation::Avg), None);
+            assert_eq!(PointBuffer::default().flush(PointAggregation::Last), None);
+        }
+
+        #[test]
+        fn no_smoothing_keeps_raw_samples() {
+            let raw = [1.0, 5.0, 3.0];
+            assert_eq!(apply_smoothing(&raw, Smoothing::None), raw);
+        }
+
+        #[test]
+        fn moving_average_uses_available_points_first() {
+            let raw = [2.0, 4.0, 6.0, 8.0];
+            assert_eq!(apply_smoothing(&raw, Smoothing::MovingAverage(2)), [2.0, 3.0, 5.0, 7.0]);
+            assert_eq!(apply_smoothing(&raw, Smoothing::MovingAverage(0)), raw);
+        }
+
+        #[test]
+        fn exponential_smoothing_follows_alpha() {
+            let raw = [0.0, 10.0, 10.0];
+            assert_eq!(apply_smoothing(&raw, Smoothing::Exponential(0.5)), [0.0, 5.0, 7.5]);
+            // Alpha hors de [0.01, 1] ramené dans l'intervalle, NaN traité comme 1
+            assert_eq!(apply_smoothing(&raw, Smoothing::Exponential(2.0)), raw);
+            assert_eq!(apply_smoothing(&raw, Smoothing::Exponential(f32::NAN)), raw);
+        }
+
+        #[test]
+        fn percentile_clamp_caps_spikes() {
+            let raw = [1.0, 2.0, 3.0, 4.0, 100.0];
+            assert_eq!(
+                apply_smoothing(&raw, Smoothing::PercentileClamp(75.0)),
+                [1.0, 2.0, 3.0, 4.0, 4.0]
+            );
+            assert_eq!(apply_smoothing(&raw, Smoothing::PercentileClamp(100.0)), raw);
+            assert!(apply_smoothing(&[], Smoothing::PercentileClamp(95.0)).is_empty());
+        }
+
+        #[test]
+        fn smoothed_latest_matches_last_drawn_point() {
+            let history = history_of(4, &[0.0, 10.0]);
+            assert_eq!(smoothed_latest(&history, Smoothing::Exponential(0.5)), Some(5.0));
+            assert_eq!(smoothed_latest(&History::new(4), Smoothing::None), None);
+
+            // Une courbe qui finit sur un trou n'a pas de dernier point tracé
+            let history = history_of(4, &[0.0, 10.0, f32::NAN]);
+            assert_eq!(smoothed_latest(&history, Smoothing::Exponential(0.5)), None);
+
+            // Après un trou, le lissage repart des seules nouvelles mesures
+            let history = history_of(8, &[90.0, 90.0, f32::NAN, 20.0, 30.0]);
+            assert_eq!(smoothed_latest(&history, Smoothing::MovingAverage(3)), Some(25.0));
+        }
+
+        #[test]
+        fn zero_capacity_history_stays_empty() {
+            let mut history = history_of(3, &[1.0, 2.0]);
+            history.set_capacity(0);
+            history.push(3.0);
+            assert!(history.is_empty());
+        }
+
+        #[test]
+        fn saved_history_keeps_gaps_through_json() {
+            let mut saved = SavedHistory::new(Duration::from_secs(1), UNIX_EPOCH);
+            saved.series.insert("cpu".to_string(), vec![1.0, f32::NAN, 3.0]);
+
+            let json = serde_json::to_string(&saved).unwrap();
+            let loaded: SavedHistory = serde_json::from_str(&json).unwrap();
+            let cpu = &loaded.series["cpu"];
+            assert_eq!((cpu[0], cpu[2]), (1.0, 3.0));
+            assert!(cpu[1].is_nan());
+        }
+
+        #[test]
+        fn gaps_are_ignored_by_std_dev_and_csv() {
+            let history = history_of(4, &[2.0, f32::NAN, 4.0]);
+            assert_eq!(std_dev(&history), 1.0);
+
+            let end = UNIX_EPOCH + Duration::from_secs(10);
+            let csv = history_csv(&[("cpu", &history)], Duration::from_secs(1), end);
+            let cells: Vec<&str> =
+                csv.lines().skip(1).map(|line| line.split(',').nth(1).unwrap()).collect();
+            assert_eq!(cells, ["2.00", "", "4.00"]);
+        }
+
+        #[test]
+        fn zero_total_memory_is_unavailable() {
+            assert!(!memory_available(0));
+            assert_eq!(memory_pressure(0, 0), None);
+            assert!(memory_available(16_384));
+            assert_eq!(memory_pressure(4_096, 16_384), Some(75.0));
+        }
+    }
+}
+
+pub use stats::{
+    apply_smoothing, busy_core_count, finite_samples, history_csv, learn_baseline, memory_available,
+    memory_pressure, moving_average, normalize, smooth_with_gaps, smoothed_latest, std_dev,
+    BaselineComparator, History, PointBuffer, SavedHistory,
+};
+
+// ============================================================================
+// MODULE BATTERIE (optionnel)
+// ============================================================================
+#[cfg(feature = "battery")]
+pub mod battery {
+    #[cfg(target_os = "linux")]
+    use std::path::Path;
+    use std::sync::Mutex;
+    #[cfg(windows)]
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+    use std::time::Duration;
+
+    /// État de la batterie. `present` est faux tant qu'aucune batterie n'a pu être lue
+    /// (machine de bureau, module indisponible) : les autres champs valent alors 100 %
+    /// sur secteur.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct BatteryInfo {
+        pub percent: f32,
+        // Secteur branché, ou batterie en charge
+        pub charging: bool,
+        // Avant décharge complète, ou avant charge complète en charge ; `None` sans estimation
+        pub time_remaining: Option<Duration>,
+        pub present: bool,
+    }
+
+    impl BatteryInfo {
+        const ABSENT: BatteryInfo = BatteryInfo {
+            percent: 100.0,
+            charging: false,
+            time_remaining: None,
+            present: false,
+        };
+    }
+
+    // Dernière lecture valide, renvoyée quand la commande échoue plutôt que 100 %
+    static LAST_KNOWN: Mutex<Option<BatteryInfo>> = Mutex::new(None);
+
+    pub fn get_battery_info_detailed() -> BatteryInfo {
+        let info = read_battery_info_detailed().ok();
+
+        let mut last_known = LAST_KNOWN.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match info {
+            Some(info) => {
+                *last_known = Some(info);
+                info
+            }
+            None => last_known.unwrap_or(BatteryInfo::ABSENT),
+        }
+    }
+
+    pub fn get_battery_info() -> (f32, bool) {
+        let info = get_battery_info_detailed();
+        (info.percent, info.charging)
+    }
+
+    /// Lecture de la batterie, avec la raison de l'échec le cas échéant.
+    pub fn read_battery_info() -> Result<(f32, bool), String> {
+        read_battery_info_detailed().map(|info| (info.percent, info.charging))
+    }
+
+    pub fn read_battery_info_detailed() -> Result<BatteryInfo, String> {
+        #[cfg(target_os = "linux")]
+        {
+            read_power_supply(Path::new(POWER_SUPPLY_DIR))
+        }
+
+        #[cfg(windows)]
+        {
+            read_power_status()
+        }
+
+        #[cfg(not(any(target_os = "linux", windows)))]
+        {
+            let output = battery_command_output()?;
+            parse_pmset(&output)
+                .ok_or_else(|| "aucune batterie interne dans la sortie de pmset".to_string())
+        }
+    }
+
+    /// Sortie brute de la source interrogée pour la batterie (`pmset -g batt` sous
+    /// macOS, fichiers de `/sys/class/power_supply` sous Linux, `GetSystemPowerStatus`
+    /// sous Windows).
+    pub fn raw_battery_output() -> Option<String> {
+        #[cfg(target_os = "linux")]
+        {
+            power_supply_dump(Path::new(POWER_SUPPLY_DIR))
+        }
+
+        #[cfg(windows)]
+        {
+            system_power_status().ok().map(|status| format!("{:?}", status))
+        }
+
+        #[cfg(not(any(target_os = "linux", windows)))]
+        {
+            battery_command_output().ok()
+        }
+    }
+
+    #[cfg(windows)]
+    fn system_power_status() -> Result<SYSTEM_POWER_STATUS, String> {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        // SAFETY : `status` est une structure valide, remplie par l'appel
+        unsafe { GetSystemPowerStatus(&mut status) }
+            .map_err(|err| format!("GetSystemPowerStatus a échoué : {}", err))?;
+        Ok(status)
+    }
+
+    /// Niveau, branchement secteur et autonomie rapportés par Windows. Les valeurs inconnues
+    /// (255) ou l'absence de batterie sont des erreurs : la dernière lecture valide,
+    /// ou la valeur par défaut, est alors conservée par `get_battery_info`.
+    #[cfg(windows)]
+    pub fn read_power_status() -> Result<BatteryInfo, String> {
+        const UNKNOWN: u8 = 255;
+        const UNKNOWN_LIFE_TIME: u32 = u32::MAX;
+        const NO_SYSTEM_BATTERY: u8 = 128;
+
+        let status = system_power_status()?;
+        if status.BatteryFlag != UNKNOWN && status.BatteryFlag & NO_SYSTEM_BATTERY != 0 {
+            return Err("aucune batterie système".to_string());
+        }
+        if status.BatteryLifePercent == UNKNOWN {
+            return Err("niveau de batterie inconnu".to_string());
+        }
+
+        Ok(BatteryInfo {
+            percent: f32::from(status.BatteryLifePercent),
+            charging: status.ACLineStatus == 1,
+            // Windows n'estime que l'autonomie sur batterie, pas la durée de charge
+            time_remaining: (status.BatteryLifeTime != UNKNOWN_LIFE_TIME)
+                .then(|| Duration::from_secs(u64::from(status.BatteryLifeTime))),
+            present: true,
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+    // Attribut d'une alimentation (`capacity`, `status`…), sans le saut de ligne final
+    #[cfg(target_os = "linux")]
+    fn supply_attribute(supply: &Path, name: &str) -> Option<String> {
+        std::fs::read_to_string(supply.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    }
+
+    // Réserve actuelle, réserve pleine et débit d'une batterie : en µWh et µW
+    // (`energy_*`, `power_now`) ou en µAh et µA (`charge_*`, `current_now`) selon le pilote
+    #[cfg(target_os = "linux")]
+    fn supply_reserve(supply: &Path) -> Option<(f64, f64, f64)> {
+        let number = |name: &str| supply_attribute(supply, name)?.parse::<f64>().ok();
+        let reserve = |now: &str, full: &str, rate: &str| {
+            Some((number(now)?, number(full)?, number(rate)?.abs()))
+        };
+        reserve("energy_now", "energy_full", "power_now")
+            .or_else(|| reserve("charge_now", "charge_full", "current_now"))
+    }
+
+    /// Batteries du système listées dans `dir` (`BAT0`, `BAT1`… quel que soit leur nom) :
+    /// moyenne de leurs pourcentages, et charge si le secteur est branché ou si l'une
+    /// d'elles charge. Les batteries de périphériques (souris, casque) sont ignorées.
+    /// La durée restante est estimée à partir des réserves cumulées et du débit actuel.
+    #[cfg(target_os = "linux")]
+    pub fn read_power_supply(dir: &Path) -> Result<BatteryInfo, String> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|err| format!("{} illisible : {}", dir.display(), err))?;
+
+        let mut capacities = Vec::new();
+        let mut charging = false;
+        let (mut stored, mut full, mut rate) = (0.0, 0.0, 0.0);
+        for supply in entries.flatten().map(|entry| entry.path()) {
+            match supply_attribute(&supply, "type").as_deref() {
+                Some("Mains") => {
+                    charging |= supply_attribute(&supply, "online").as_deref() == Some("1");
+                }
+                Some("Battery") if supply_attribute(&supply, "scope").as_deref() != Some("Device") => {
+                    if let Some(capacity) =
+                        supply_attribute(&supply, "capacity").and_then(|value| value.parse::<f32>().ok())
+                    {
+                        capacities.push(capacity);
+                    }
+                    if let Some((now, capacity, draw)) = supply_reserve(&supply) {
+                        stored += now;
+                        full += capacity;
+                        rate += draw;
+                    }
+                    charging |= matches!(
+                        supply_attribute(&supply, "status").as_deref(),
+                        Some("Charging" | "Full")
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if capacities.is_empty() {
+            return Err(format!("aucune batterie dans {}", dir.display()));
+        }
+        let percent = capacities.iter().sum::<f32>() / capacities.len() as f32;
+
+        // Débit nul : batterie pleine sur secteur, ou pilote sans mesure
+        let time_remaining = (rate > 0.0).then(|| {
+            let reserve = if charging { (full - stored).max(0.0) } else { stored };
+            Duration::from_secs_f64(reserve / rate * 3600.0)
+        });
+
+        Ok(BatteryInfo {
+            percent,
+            charging,
+            time_remaining,
+            present: true,
+        })
+    }
+
+    // Attributs utiles de chaque alimentation, pour le rapport de diagnostic
+    #[cfg(target_os = "linux")]
+    fn power_supply_dump(dir: &Path) -> Option<String> {
+        let mut supplies: Vec<_> = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        supplies.sort();
+
+        let mut dump = String::new();
+        for supply in supplies {
+            let name = supply.file_name()?.to_string_lossy().into_owned();
+            for attribute in [
+                "type",
+                "scope",
+                "online",
+                "capacity",
+                "status",
+                "energy_now",
+                "energy_full",
+                "power_now",
+                "charge_now",
+                "charge_full",
+                "current_now",
+            ] {
+                if let Some(value) = supply_attribute(&supply, attribute) {
+                    dump.push_str(&format!("{}/{}: {}\n", name, attribute, value));
+                }
+            }
+        }
+        Some(dump)
+    }
+
+    // Sous charge, pmset peut échouer ou bloquer : un second essai est tenté après un court délai
+    #[cfg(not(any(target_os = "linux", windows)))]
+    fn battery_command_output() -> Result<String, String> {
+        #[cfg(target_os = "macos")]
+        {
+            run_pmset().or_else(|_| {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                run_pmset()
+            })
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Err("lecture non prise en charge sur cette plateforme".to_string())
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn run_pmset() -> Result<String, String> {
+        use std::io::Read;
+        use std::process::{Command, Stdio};
+        use wait_timeout::ChildExt;
+
+        const PMSET_TIMEOUT: Duration = Duration::from_millis(500);
+
+        let mut child = Command::new("pmset")
+            .arg("-g")
+            .arg("batt")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|err| format!("pmset introuvable : {}", err))?;
+
+        match child.wait_timeout(PMSET_TIMEOUT).map_err(|err| err.to_string())? {
+            Some(status) if status.success() => {
+                let mut stdout = String::new();
+                if let Some(mut pipe) = child.stdout.take() {
+                    pipe.read_to_string(&mut stdout).map_err(|err| err.to_string())?;
+                }
+                Ok(stdout)
+            }
+            Some(status) => Err(format!("pmset a échoué ({})", status)),
+            None => {
+                let _ = child.kill();
+                let _ = child.wait();
+                Err("pmset ne répond pas".to_string())
+            }
+        }
+    }
+
+    pub fn parse_battery_output(stdout: &str) -> Option<(f32, bool)> {
+        parse_pmset(stdout).map(|battery| (battery.percent, battery.charging))
+    }
+
+    // Exemple de sortie :
+    //   Now drawing from 'Battery Power'
+    //    -InternalBattery-0 (id=1234567)	85%; discharging; 4:12 remaining present: true
+    // Juste après un (dé)branchement, l'état est transitoire (« (no estimate) »,
+    // « calculating ») et peut encore annoncer l'ancien état : seul l'en-tête fait
+    // alors foi pour la charge, sans durée restante.
+    pub fn parse_pmset(stdout: &str) -> Option<BatteryInfo> {
+        let ac_power = stdout
+            .lines()
+            .next()
+            .is_some_and(|header| header.contains("AC Power"));
+
+        let line = stdout
+            .lines()
+            .find(|line| line.contains("InternalBattery") && line.contains('%'))?;
+        let percent = line
+            .split_whitespace()
+            .find(|part| part.ends_with("%;") || part.ends_with('%'))?
+            .trim_end_matches(';')
+            .trim_end_matches('%')
+            .parse::<f32>()
+            .ok()?;
+
+        // Champs après le pourcentage : état, puis durée restante
+        let mut fields = line.split(';').skip(1).map(str::trim);
+        let state = fields.next().unwrap_or_default();
+        let remaining = fields.next().unwrap_or_default();
+
+        if line.contains("(no estimate)") || line.contains("calculating") {
+            return Some(BatteryInfo {
+                percent,
+                charging: ac_power,
+                time_remaining: None,
+                present: true,
+            });
+        }
+
+        let charging = ac_power || matches!(state, "charging" | "charged" | "finishing charge");
+        // Chargée : pmset annonce « 0:00 remaining », qui n'est pas une durée à afficher
+        let time_remaining = if state == "charged" {
+            None
+        } else {
+            parse_remaining(remaining)
+        };
+        Some(BatteryInfo {
+            percent,
+            charging,
+            time_remaining,
+            present: true,
+        })
+    }
+
+    // « 4:12 remaining present: true » -> 4 h 12 min
+    fn parse_remaining(field: &str) -> Option<Duration> {
+        let (hours, minutes) = field.split_whitespace().next()?.split_once(':')?;
+        let minutes = hours.parse::<u64>().ok()? * 60 + minutes.parse::<u64>().ok()?;
+        Some(Duration::from_secs(minutes * 60))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn pmset_calculating_has_no_estimate() {
+            let stdout = "Now drawing from 'AC Power'\n \
+                -InternalBattery-0 (id=1234567)\t64%; discharging; (no estimate) present: true\n";
+            let battery = parse_pmset(stdout).unwrap();
+            assert_eq!(battery.percent, 64.0);
+            assert!(battery.charging);
+            assert_eq!(battery.time_remaining, None);
+
+            let stdout = "Now drawing from 'Battery Power'\n \
+                -InternalBattery-0 (id=1234567)\t37%; charging; calculating remaining present: true\n";
+            let battery = parse_pmset(stdout).unwrap();
+            assert_eq!(battery.percent, 37.0);
+            // L'en-tête fait foi : l'état « charging » est encore celui d'avant le débranchement
+            assert!(!battery.charging);
+            assert_eq!(battery.time_remaining, None);
+        }
+
+        #[test]
+        fn pmset_charged() {
+            let stdout = "Now drawing from 'AC Power'\n \
+                -InternalBattery-0 (id=1234567)\t100%; charged; 0:00 remaining present: true\n";
+            let battery = parse_pmset(stdout).unwrap();
+            assert_eq!(battery.percent, 100.0);
+            assert!(battery.charging);
+            assert!(battery.present);
+            assert_eq!(battery.time_remaining, None);
+        }
+
+        #[test]
+        fn pmset_charging() {
+            let stdout = "Now drawing from 'AC Power'\n \
+                -InternalBattery-0 (id=1234567)\t42%; charging; 1:05 remaining present: true\n";
+            let battery = parse_pmset(stdout).unwrap();
+            assert_eq!(battery.percent, 42.0);
+            assert!(battery.charging);
+            assert_eq!(battery.time_remaining, Some(Duration::from_secs(65 * 60)));
+        }
+
+        #[test]
+        fn pmset_discharging() {
+            let stdout = "Now drawing from 'Battery Power'\n \
+                -InternalBattery-0 (id=1234567)\t85%; discharging; 4:12 remaining present: true\n";
+            let battery = parse_pmset(stdout).unwrap();
+            assert_eq!(battery.percent, 85.0);
+            assert!(!battery.charging);
+            assert_eq!(battery.time_remaining, Some(Duration::from_secs((4 * 60 + 12) * 60)));
+        }
+    }
+}
+
+#[cfg(feature = "battery")]
+pub use battery::{
+    get_battery_info, get_battery_info_detailed, raw_battery_output, read_battery_info,
+    read_battery_info_detailed, BatteryInfo,
+};
+
+#[cfg(not(feature = "battery"))]
+pub fn get_battery_info() -> (f32, bool) {
+    (100.0, false)
+}
+
+#[cfg(not(feature = "battery"))]
+pub fn read_battery_info() -> Result<(f32, bool), String> {
+    Err("module batterie désactivé".to_string())
+}
+
+#[cfg(not(feature = "battery"))]
+pub fn raw_battery_output() -> Option<String> {
+    None
+}
+
+// ============================================================================
+// MODULE RÉSEAU (optionnel)
+// ============================================================================
+// Nom d'interface et octets reçus/émis depuis le dernier rafraîchissement
+pub type InterfaceDelta = (String, u64, u64);
+
+#[cfg(feature = "network")]
+pub mod network {
+    use super::{Icon, InterfaceDelta};
+    use std::collections::HashMap;
+    use sysinfo::Networks;
+
+    pub fn network_deltas(networks: &Networks) -> (u64, u64) {
+        let mut rx = 0;
+        let mut tx = 0;
+
+        for (_name, data) in networks {
+            rx += data.received();
+            tx += data.transmitted();
+        }
+
+        (rx, tx)
+    }
+
+    pub fn network_totals(networks: &Networks) -> (f32, f32) {
+        let mut rx = 0_u64;
+        let mut tx = 0_u64;
+
+        for (_name, data) in networks {
+            rx += data.total_received();
+            tx += data.total_transmitted();
+        }
+
+        (
+            rx as f32 / 1_073_741_824.0,
+            tx as f32 / 1_073_741_824.0,
+        )
+    }
+
+    /// Octets reçus/émis depuis le démarrage, limités à `allowlist` si elle est fournie.
+    pub fn network_total_bytes(networks: &Networks, allowlist: Option<&[String]>) -> (u64, u64) {
+        networks
+            .iter()
+            .filter(|(name, _)| is_allowed_interface(name, allowlist))
+            .fold((0, 0), |(rx, tx), (_, data)| {
+                (rx + data.total_received(), tx + data.total_transmitted())
+            })
+    }
+
+    /// Vrai si `name` figure dans `allowlist`, ou si aucune liste n'est fournie.
+    pub fn is_allowed_interface(name: &str, allowlist: Option<&[String]>) -> bool {
+        allowlist.is_none_or(|names| names.iter().any(|allowed| allowed == name))
+    }
+
+    /// Vrai si `name` commence par l'un des préfixes de `exclude` (`lo`, `docker`…).
+    pub fn is_excluded_interface(name: &str, exclude: &[&str]) -> bool {
+        exclude.iter().any(|prefix| name.starts_with(prefix))
+    }
+
+    /// Comme `network_deltas`, sans les interfaces dont le nom commence par un
+    /// préfixe de `exclude` (boucle locale, ponts et interfaces de conteneurs).
+    pub fn network_deltas_filtered(networks: &Networks, exclude: &[&str]) -> (u64, u64) {
+        sum_deltas_excluding(
+            networks
+                .iter()
+                .map(|(name, data)| (name.as_str(), data.received(), data.transmitted())),
+            exclude,
+        )
+    }
+
+    // Somme des (reçus, émis) des interfaces non exclues
+    fn sum_deltas_excluding<'a>(
+        deltas: impl IntoIterator<Item = (&'a str, u64, u64)>,
+        exclude: &[&str],
+    ) -> (u64, u64) {
+        deltas
+            .into_iter()
+            .filter(|(name, _, _)| !is_excluded_interface(name, exclude))
+            .fold((0, 0), |(rx, tx), (_, received, sent)| (rx + received, tx + sent))
+    }
+
+    /// Comme `network_deltas`, limité aux interfaces listées dans `names`.
+    pub fn network_deltas_for(networks: &Networks, names: &[String]) -> (u64, u64) {
+        networks
+            .iter()
+            .filter(|(name, _)| names.contains(name))
+            .fold((0, 0), |(rx, tx), (_, data)| (rx + data.received(), tx + data.transmitted()))
+    }
+
+    /// Comme `network_totals`, limité aux interfaces listées dans `names`.
+    pub fn network_totals_for(networks: &Networks, names: &[String]) -> (f32, f32) {
+        let (rx, tx) = networks
+            .iter()
+            .filter(|(name, _)| names.contains(name))
+            .fold((0_u64, 0_u64), |(rx, tx), (_, data)| {
+                (rx + data.total_received(), tx + data.total_transmitted())
+            });
+
+        (
+            rx as f32 / 1_073_741_824.0,
+            tx as f32 / 1_073_741_824.0,
+        )
+    }
+
+    /// Part de la capacité du lien utilisée, en %, plafonnée à 100.
+    pub fn net_utilization(mbps: f32, capacity: f32) -> f32 {
+        if capacity <= 0.0 {
+            return 0.0;
+        }
+
+        (mbps / capacity * 100.0).clamp(0.0, 100.0)
+    }
+
+    // Suit les compteurs cumulés de chaque interface pour calculer les deltas soi-même :
+    // un compteur qui diminue (interface réinitialisée, Wi-Fi reconnecté) ou une interface
+    // qui apparaît compte pour zéro sur ce tick au lieu de produire un pic aberrant.
+    #[derive(Debug, Default)]
+    pub struct RateTracker {
+        previous: HashMap<String, (u64, u64)>,
+    }
+
+    impl RateTracker {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Octets reçus/émis depuis l'appel précédent, toutes interfaces confondues.
+        pub fn update(&mut self, networks: &Networks) -> (u64, u64) {
+            self.update_for(networks, None)
+        }
+
+        /// Comme `update`, limité aux interfaces de `allowlist` si elle est fournie.
+        pub fn update_for(&mut self, networks: &Networks, allowlist: Option<&[String]>) -> (u64, u64) {
+            self.update_counters(
+                networks
+                    .iter()
+                    .filter(|(name, _)| is_allowed_interface(name, allowlist))
+                    .map(|(name, data)| (name.as_str(), data.total_received(), data.total_transmitted())),
+            )
+        }
+
+        pub fn update_counters<'a>(
+            &mut self,
+            counters: impl IntoIterator<Item = (&'a str, u64, u64)>,
+        ) -> (u64, u64) {
+            let mut rx = 0;
+            let mut tx = 0;
+            let mut current = HashMap::new();
+
+            for (name, total_rx, total_tx) in counters {
+                // Un compteur qui diminue a été remis à zéro : saturating_sub donne 0
+                if let Some(&(prev_rx, prev_tx)) = self.previous.get(name) {
+                    rx += total_rx.saturating_sub(prev_rx);
+                    tx += total_tx.saturating_sub(prev_tx);
+                }
+                current.insert(name.to_string(), (total_rx, total_tx));
+            }
+
+            self.previous = current;
+            (rx, tx)
+        }
+    }
+
+    // Une autre interface doit dépasser l'interface courante de ce facteur
+    // pour la remplacer, afin d'éviter d'osciller entre deux interfaces proches.
+    const DOMINANT_STICKINESS: f32 = 1.5;
+
+    /// Interface ayant eu le plus d'activité depuis le dernier rafraîchissement.
+    pub fn dominant_interface(networks: &Networks, prev: &Option<String>) -> Option<String> {
+        let activity = |name: &str| {
+            networks
+                .list()
+                .get(name)
+                .map(|data| data.received() + data.transmitted())
+        };
+
+        let busiest = networks
+            .iter()
+            .map(|(name, data)| (name, data.received() + data.transmitted()))
+            .max_by_key(|(_name, bytes)| *bytes);
+
+        let prev_activity = prev.as_deref().and_then(activity);
+
+        match (busiest, prev_activity) {
+            (None, _) => None,
+            (Some((_name, 0)), Some(_)) => prev.clone(),
+            (Some((_name, 0)), None) => None,
+            (Some((name, bytes)), Some(prev_bytes)) => {
+                if bytes as f32 > prev_bytes as f32 * DOMINANT_STICKINESS {
+                    Some(name.clone())
+                } else {
+                    prev.clone()
+                }
+            }
+            (Some((name, _bytes)), None) => Some(name.clone()),
+        }
+    }
+
+    /// Octets reçus/émis par interface depuis le dernier rafraîchissement,
+    /// de la plus active à la moins active.
+    pub fn per_interface_deltas(networks: &Networks) -> Vec<(String, u64, u64)> {
+        let mut deltas: Vec<(String, u64, u64)> = networks
+            .iter()
+            .map(|(name, data)| (name.clone(), data.received(), data.transmitted()))
+            .collect();
+
+        deltas.sort_by(|a, b| (b.1 + b.2).cmp(&(a.1 + a.2)).then_with(|| a.0.cmp(&b.0)));
+        deltas
+    }
+
+    /// Interface la plus active et sa part du trafic total (entre 0 et 1).
+    pub fn top_contributor(deltas: &[(String, u64, u64)]) -> Option<(String, f32)> {
+        let total: u64 = deltas.iter().map(|(_, rx, tx)| rx + tx).sum();
+        if total == 0 {
+            return None;
+        }
+
+        deltas
+            .iter()
+            .max_by_key(|(_, rx, tx)| rx + tx)
+            .map(|(name, rx, tx)| (name.clone(), (rx + tx) as f32 / total as f32))
+    }
+
+    /// Les `max` premières interfaces de `deltas` (triées par activité) et le
+    /// cumul des suivantes, `None` s'il n'y en a pas.
+    pub fn top_interfaces(
+        deltas: &[InterfaceDelta],
+        max: usize,
+    ) -> (&[InterfaceDelta], Option<(u64, u64)>) {
+        let (shown, rest) = deltas.split_at(max.min(deltas.len()));
+        let others = (!rest.is_empty()).then(|| {
+            rest.iter()
+                .fold((0, 0), |(rx, tx), (_, delta_rx, delta_tx)| (rx + delta_rx, tx + delta_tx))
+        });
+
+        (shown, others)
+    }
+
+    pub fn interface_deltas(networks: &Networks, name: &str) -> Option<(u64, u64)> {
+        networks
+            .list()
+            .get(name)
+            .map(|data| (data.received(), data.transmitted()))
+    }
+
+    /// Type d'interface deviné d'après son nom (sysinfo ne le fournit pas).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum IfaceKind {
+        Wired,
+        Wireless,
+        Vpn,
+        Unknown,
+    }
+
+    impl IfaceKind {
+        pub fn icon(self) -> Option<Icon> {
+            match self {
+                IfaceKind::Wired => Some(Icon::Wired),
+                IfaceKind::Wireless => Some(Icon::Wireless),
+                IfaceKind::Vpn => Some(Icon::Vpn),
+                IfaceKind::Unknown => None,
+            }
+        }
+    }
+
+    // Préfixes de noms (en minuscules), testés dans l'ordre : les plus longs d'abord
+    // quand l'un en contient un autre. Un nouveau nommage s'ajoute ici.
+    const INTERFACE_PREFIXES: &[(&str, IfaceKind)] = &[
+        ("wlan", IfaceKind::Wireless),
+        ("wlp", IfaceKind::Wireless),
+        ("wl", IfaceKind::Wireless),
+        ("wifi", IfaceKind::Wireless),
+        ("wi-fi", IfaceKind::Wireless),
+        ("awdl", IfaceKind::Wireless),
+        ("eth", IfaceKind::Wired),
+        ("enp", IfaceKind::Wired),
+        ("eno", IfaceKind::Wired),
+        ("ens", IfaceKind::Wired),
+        ("enx", IfaceKind::Wired),
+        ("utun", IfaceKind::Vpn),
+        ("tun", IfaceKind::Vpn),
+        ("tap", IfaceKind::Vpn),
+        ("wg", IfaceKind::Vpn),
+        ("ppp", IfaceKind::Vpn),
+        ("ipsec", IfaceKind::Vpn),
+    ];
+
+    pub fn classify_interface(name: &str) -> IfaceKind {
+        let name = name.to_ascii_lowercase();
+        let known = INTERFACE_PREFIXES
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix));
+        if let Some(&(_, kind)) = known {
+            return kind;
+        }
+
+        // `enN` : Ethernet sous Linux (ancien nommage). Sous macOS, en0 est le Wi-Fi
+        // des portables mais l'Ethernet des ordinateurs de bureau, et en1 l'inverse :
+        // le nom seul ne permet pas de trancher.
+        if name.starts_with("en") && !cfg!(target_os = "macos") {
+            return IfaceKind::Wired;
+        }
+
+        IfaceKind::Unknown
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn counter_reset_counts_as_zero() {
+            let mut tracker = RateTracker::new();
+            assert_eq!(tracker.update_counters([("eth0", 1_000, 500)]), (0, 0));
+            assert_eq!(tracker.update_counters([("eth0", 1_600, 700)]), (600, 200));
+
+            // Interface réinitialisée : les compteurs repartent de zéro
+            assert_eq!(tracker.update_counters([("eth0", 40, 10)]), (0, 0));
+            assert_eq!(tracker.update_counters([("eth0", 140, 30)]), (100, 20));
+        }
+
+        #[test]
+        fn allowlist_keeps_only_listed_interfaces() {
+            let allowlist = vec!["eth0".to_string(), "wlan0".to_string()];
+            let counters = |step: u64| {
+                [
+                    ("lo", 5_000 * step, 5_000 * step),
+                    ("eth0", 1_000 * step, 100 * step),
+                    ("docker0", 300 * step, 300 * step),
+                    ("wlan0", 200 * step, 20 * step),
+                    ("utun3", 50 * step, 50 * step),
+                ]
+            };
+            let allowed = |step: u64| {
+                counters(step)
+                    .into_iter()
+                    .filter(|(name, _, _)| is_allowed_interface(name, Some(&allowlist)))
+            };
+
+            let mut tracker = RateTracker::new();
+            tracker.update_counters(allowed(1));
+            assert_eq!(tracker.update_counters(allowed(2)), (1_200, 120));
+
+            assert!(is_allowed_interface("docker0", None));
+            assert!(!is_allowed_interface("eth1", Some(&allowlist)));
+        }
+
+        #[test]
+        fn filtered_deltas_skip_loopback_bridges_and_containers() {
+            let exclude = ["lo", "docker", "veth", "br-"];
+            let deltas = [
+                ("lo", 5_000, 5_000),
+                ("docker0", 300, 300),
+                ("veth1a2b3c", 70, 70),
+                ("br-4f1e2d", 40, 40),
+                ("eth0", 1_000, 100),
+                ("wlan0", 200, 20),
+            ];
+
+            assert_eq!(sum_deltas_excluding(deltas, &exclude), (1_200, 120));
+            assert_eq!(sum_deltas_excluding(deltas, &[]), (6_610, 5_530));
+        }
+
+        #[test]
+        fn top_interfaces_groups_the_rest_as_others() {
+            let deltas: Vec<InterfaceDelta> = [
+                ("eth0", 900, 90),
+                ("wlan0", 500, 50),
+                ("utun3", 300, 30),
+                ("docker0", 200, 20),
+                ("lo", 100, 10),
+                ("awdl0", 40, 4),
+            ]
+            .into_iter()
+            .map(|(name, rx, tx)| (name.to_string(), rx, tx))
+            .collect();
+
+            let (shown, others) = top_interfaces(&deltas, 4);
+            let names: Vec<&str> = shown.iter().map(|(name, _, _)| name.as_str()).collect();
+            assert_eq!(names, ["eth0", "wlan0", "utun3", "docker0"]);
+            assert_eq!(others, Some((140, 14)));
+
+            let (shown, others) = top_interfaces(&deltas, 6);
+            assert_eq!(shown.len(), 6);
+            assert_eq!(others, None);
+        }
+    }
+}
+
 #[cfg(feature = "network")]
-use sysinfo::Networks;
+pub use network::{
+    classify_interface, dominant_interface, interface_deltas, is_allowed_interface,
+    is_excluded_interface, network_deltas,
+    network_deltas_filtered, network_deltas_for, network_totals, network_total_bytes,
+    network_totals_for, net_utilization, per_interface_deltas, top_contributor, top_interfaces,
+    IfaceKind, RateTracker,
+};
+
+#[cfg(not(feature = "network"))]
+pub fn network_deltas(_networks: &sysinfo::Networks) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn network_totals(_networks: &sysinfo::Networks) -> (f32, f32) {
+    (0.0, 0.0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn network_total_bytes(_networks: &sysinfo::Networks, _allowlist: Option<&[String]>) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn is_allowed_interface(_name: &str, _allowlist: Option<&[String]>) -> bool {
+    true
+}
+
+#[cfg(not(feature = "network"))]
+pub fn is_excluded_interface(_name: &str, _exclude: &[&str]) -> bool {
+    false
+}
+
+#[cfg(not(feature = "network"))]
+pub fn network_deltas_filtered(_networks: &sysinfo::Networks, _exclude: &[&str]) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn network_deltas_for(_networks: &sysinfo::Networks, _names: &[String]) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn network_totals_for(_networks: &sysinfo::Networks, _names: &[String]) -> (f32, f32) {
+    (0.0, 0.0)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn net_utilization(_mbps: f32, _capacity: f32) -> f32 {
+    0.0
+}
+
+#[cfg(not(feature = "network"))]
+pub fn dominant_interface(_networks: &sysinfo::Networks, _prev: &Option<String>) -> Option<String> {
+    None
+}
+
+#[cfg(not(feature = "network"))]
+pub fn per_interface_deltas(_networks: &sysinfo::Networks) -> Vec<(String, u64, u64)> {
+    Vec::new()
+}
+
+#[cfg(not(feature = "network"))]
+pub fn top_contributor(_deltas: &[(String, u64, u64)]) -> Option<(String, f32)> {
+    None
+}
+
+#[cfg(not(feature = "network"))]
+pub fn top_interfaces(
+    deltas: &[InterfaceDelta],
+    _max: usize,
+) -> (&[InterfaceDelta], Option<(u64, u64)>) {
+    (&deltas[..0], None)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn interface_deltas(_networks: &sysinfo::Networks, _name: &str) -> Option<(u64, u64)> {
+    None
+}
+
+// ============================================================================
+// MODULE NETSTAT (optionnel)
+// ============================================================================
+// Répartition approximative IPv4 / IPv6 des octets échangés depuis le démarrage.
+// sysinfo ne l'expose pas : on lit les compteurs du noyau Linux, `None` ailleurs.
+#[cfg(feature = "netstat")]
+pub mod netstat {
+    /// Octets cumulés (reçus + émis) en IPv4 et en IPv6.
+    pub fn ip_version_bytes() -> Option<(u64, u64)> {
+        #[cfg(target_os = "linux")]
+        {
+            // /proc/net/snmp ne compte que des paquets pour IPv4 : les octets sont
+            // dans la section IpExt de /proc/net/netstat.
+            let netstat = std::fs::read_to_string("/proc/net/netstat").ok()?;
+            let snmp6 = std::fs::read_to_string("/proc/net/snmp6").ok()?;
+
+            let ipv4 = ip_ext_octets(&netstat)?;
+            let ipv6 = snmp6_octets(&snmp6)?;
+
+            Some((ipv4, ipv6))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    // Format : une ligne d'en-têtes "IpExt: InOctets OutOctets ..." suivie d'une ligne de valeurs
+    #[cfg(target_os = "linux")]
+    fn ip_ext_octets(content: &str) -> Option<u64> {
+        let mut lines = content.lines().filter(|line| line.starts_with("IpExt:"));
+        let headers = lines.next()?.split_whitespace();
+        let values = lines.next()?.split_whitespace();
+
+        let mut total = 0;
+        for (header, value) in headers.zip(values) {
+            if header == "InOctets" || header == "OutOctets" {
+                total += value.parse::<u64>().ok()?;
+            }
+        }
+
+        Some(total)
+    }
+
+    // Format : une paire "Ip6InOctets <valeur>" par ligne
+    #[cfg(target_os = "linux")]
+    fn snmp6_octets(content: &str) -> Option<u64> {
+        let mut total = 0;
+        let mut found = false;
+
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            if let (Some("Ip6InOctets" | "Ip6OutOctets"), Some(value)) = (parts.next(), parts.next()) {
+                total += value.parse::<u64>().ok()?;
+                found = true;
+            }
+        }
+
+        found.then_some(total)
+    }
+}
+
+#[cfg(feature = "netstat")]
+pub use netstat::ip_version_bytes;
+
+#[cfg(not(feature = "netstat"))]
+pub fn ip_version_bytes() -> Option<(u64, u64)> {
+    None
+}
+
+// ============================================================================
+// MODULE DISQUE (optionnel)
+// ============================================================================
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DiskUsage {
+    pub percent: f32,
+    // En Go, non arrondis : une partition de 512 Mo ne doit pas afficher « 0 Go »
+    pub used_gb: f32,
+    pub total_gb: f32,
+}
+
+impl DiskUsage {
+    pub fn from_bytes(used_space: u64, total_space: u64) -> Self {
+        let percent = if total_space > 0 {
+            (used_space as f32 / total_space as f32) * 100.0
+        } else {
+            0.0
+        };
+
+        Self {
+            percent,
+            used_gb: used_space as f32 / 1_073_741_824.0,
+            total_gb: total_space as f32 / 1_073_741_824.0,
+        }
+    }
+}
+
+#[cfg(feature = "disk")]
+pub mod disk {
+    use super::{DiskScope, DiskUsage};
+    use std::collections::HashSet;
+    use std::path::Path;
+    use sysinfo::Disks;
+
+    #[cfg(windows)]
+    const ROOT_MOUNT: &str = "C:\\";
+    #[cfg(not(windows))]
+    const ROOT_MOUNT: &str = "/";
+
+    pub fn get_disk_usage(disks: &Disks) -> (f32, f32, f32) {
+        let mut total_space = 0_u64;
+        let mut used_space = 0_u64;
+
+        for disk in disks {
+            total_space += disk.total_space();
+            used_space += disk.total_space() - disk.available_space();
+        }
+
+        let usage = DiskUsage::from_bytes(used_space, total_space);
+        (usage.percent, usage.used_gb, usage.total_gb)
+    }
+
+    /// Octets lus/écrits depuis le dernier rafraîchissement, tous volumes confondus,
+    /// comme `network_deltas` pour le réseau. Appeler `disks.refresh(true)` avant :
+    /// juste après `Disks::new_with_refreshed_list()`, la valeur est le cumul depuis
+    /// le démarrage. Un périphérique monté plusieurs fois n'est compté qu'une fois.
+    pub fn disk_io_deltas(disks: &Disks) -> (u64, u64) {
+        let mut seen = HashSet::new();
+        disks
+            .iter()
+            .filter(|disk| seen.insert((disk.name(), disk.total_space())))
+            .fold((0, 0), |(read, written), disk| {
+                let usage = disk.usage();
+                (read + usage.read_bytes, written + usage.written_bytes)
+            })
+    }
+
+    /// Utilisation de chaque volume, avec son point de montage. Les volumes sans
+    /// capacité et ceux en lecture seule (images montées, snaps), toujours pleins,
+    /// sont ignorés.
+    pub fn per_disk_usage(disks: &Disks) -> Vec<(String, DiskUsage)> {
+        disks
+            .iter()
+            .filter(|disk| disk.total_space() > 0 && !disk.is_read_only())
+            .map(|disk| {
+                (
+                    disk.mount_point().to_string_lossy().into_owned(),
+                    DiskUsage::from_bytes(
+                        disk.total_space() - disk.available_space(),
+                        disk.total_space(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// Utilisation du volume système (`/`, ou `C:\` sous Windows), s'il est identifiable.
+    pub fn root_volume_usage(disks: &Disks) -> Option<DiskUsage> {
+        disks
+            .iter()
+            .find(|disk| disk.mount_point() == Path::new(ROOT_MOUNT))
+            .map(|disk| {
+                DiskUsage::from_bytes(
+                    disk.total_space() - disk.available_space(),
+                    disk.total_space(),
+                )
+            })
+    }
+
+    // Le volume système retombe sur l'agrégat quand la racine est introuvable
+    pub fn scoped_disk_usage(disks: &Disks, scope: DiskScope) -> DiskUsage {
+        let root = match scope {
+            DiskScope::System => root_volume_usage(disks),
+            DiskScope::All => None,
+        };
+
+        root.unwrap_or_else(|| {
+            let (percent, used_gb, total_gb) = get_disk_usage(disks);
+            DiskUsage {
+                percent,
+                used_gb,
+                total_gb,
+            }
+        })
+    }
+}
+
+#[cfg(feature = "disk")]
+pub use disk::{disk_io_deltas, get_disk_usage, per_disk_usage, root_volume_usage, scoped_disk_usage};
+
+#[cfg(not(feature = "disk"))]
+pub fn get_disk_usage(_disks: &sysinfo::Disks) -> (f32, f32, f32) {
+    (0.0, 0.0, 0.0)
+}
+
+#[cfg(not(feature = "disk"))]
+pub fn disk_io_deltas(_disks: &sysinfo::Disks) -> (u64, u64) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "disk"))]
+pub fn per_disk_usage(_disks: &sysinfo::Disks) -> Vec<(String, DiskUsage)> {
+    Vec::new()
+}
+
+#[cfg(not(feature = "disk"))]
+pub fn root_volume_usage(_disks: &sysinfo::Disks) -> Option<DiskUsage> {
+    None
+}
+
+#[cfg(not(feature = "disk"))]
+pub fn scoped_disk_usage(_disks: &sysinfo::Disks, _scope: DiskScope) -> DiskUsage {
+    DiskUsage::default()
+}
+
+// ============================================================================
+// MODULE PROCESSUS (optionnel)
+// ============================================================================
+// La liste des processus est coûteuse à rafraîchir : les appelants la mettent
+// à jour moins souvent que les autres métriques.
+#[cfg(feature = "processes")]
+pub mod processes {
+    use std::collections::HashSet;
+    use sysinfo::{ProcessesToUpdate, System};
+
+    pub fn refresh_processes(sys: &mut System) {
+        sys.refresh_processes(ProcessesToUpdate::All, true);
+    }
+
+    /// Nombre de processus et de threads. Les threads ne sont exposés que sous
+    /// Linux : ailleurs le second nombre vaut 0.
+    pub fn process_counts(sys: &System) -> (usize, usize) {
+        let mut processes = 0;
+        let mut threads = 0;
+
+        for process in sys.processes().values() {
+            // Sous Linux, sysinfo liste aussi les threads comme des processus
+            if process.thread_kind().is_some() {
+                continue;
+            }
+
+            processes += 1;
+            threads += process.tasks().map_or(0, |tasks| tasks.len());
+        }
+
+        (processes, threads)
+    }
+
+    /// Pids des processus (threads exclus) après le dernier rafraîchissement de la liste.
+    pub fn process_pids(sys: &System) -> HashSet<u32> {
+        sys.processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .map(|(pid, _)| pid.as_u32())
+            .collect()
+    }
+
+    /// Processus apparus et disparus entre deux relevés : (lancés, terminés). Un
+    /// processus éphémère lancé puis terminé entre les deux n'est pas vu.
+    pub fn process_churn(prev: &HashSet<u32>, cur: &HashSet<u32>) -> (usize, usize) {
+        (cur.difference(prev).count(), prev.difference(cur).count())
+    }
+
+    /// Processus ayant le plus écrit sur disque depuis le précédent rafraîchissement
+    /// de la liste : (nom, pid, octets écrits), par ordre décroissant. Les processus
+    /// terminés entre-temps ont été retirés par `refresh_processes`.
+    pub fn top_processes_by_disk_write(sys: &System, n: usize) -> Vec<(String, u32, u64)> {
+        let mut writers: Vec<_> = sys
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .map(|(pid, process)| {
+                (
+                    process.name().to_string_lossy().into_owned(),
+                    pid.as_u32(),
+                    process.disk_usage().written_bytes,
+                )
+            })
+            .filter(|(_, _, written)| *written > 0)
+            .collect();
+
+        writers.sort_by_key(|(_, _, written)| std::cmp::Reverse(*written));
+        writers.truncate(n);
+        writers
+    }
+
+    /// Processus les plus gourmands en CPU depuis le précédent rafraîchissement de
+    /// la liste : (nom, CPU en %, mémoire en Mo). Le pourcentage peut dépasser 100
+    /// pour un processus occupant plusieurs cœurs.
+    pub fn top_processes(sys: &System, n: usize) -> Vec<(String, f32, u64)> {
+        let mut processes: Vec<_> = sys
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| {
+                (
+                    process.name().to_string_lossy().into_owned(),
+                    process.cpu_usage(),
+                    process.memory() / 1024 / 1024,
+                )
+            })
+            .collect();
+
+        processes.sort_by(|a, b| b.1.total_cmp(&a.1));
+        processes.truncate(n);
+        processes
+    }
+}
+
+#[cfg(feature = "processes")]
+pub use processes::{
+    process_churn, process_counts, process_pids, refresh_processes, top_processes,
+    top_processes_by_disk_write,
+};
+
+#[cfg(not(feature = "processes"))]
+pub fn refresh_processes(_sys: &mut sysinfo::System) {}
+
+#[cfg(not(feature = "processes"))]
+pub fn process_counts(_sys: &sysinfo::System) -> (usize, usize) {
+    (0, 0)
+}
+
+#[cfg(not(feature = "processes"))]
+pub fn top_processes_by_disk_write(_sys: &sysinfo::System, _n: usize) -> Vec<(String, u32, u64)> {
+    Vec::new()
+}
+
+#[cfg(not(feature = "processes"))]
+pub fn top_processes(_sys: &sysinfo::System, _n: usize) -> Vec<(String, f32, u64)> {
+    Vec::new()
+}
+
+#[cfg(not(feature = "processes"))]
+pub fn process_pids(_sys: &sysinfo::System) -> std::collections::HashSet<u32> {
+    std::collections::HashSet::new()
+}
+
+#[cfg(not(feature = "processes"))]
+pub fn process_churn(
+    _prev: &std::collections::HashSet<u32>,
+    _cur: &std::collections::HashSet<u32>,
+) -> (usize, usize) {
+    (0, 0)
+}
+
+/// Données chargées par `System::new_with_specifics` : usage CPU, RAM et swap, plus
+/// la liste des processus (avec leurs threads, leur CPU, leur mémoire et leurs E/S
+/// disque, dont le premier relevé sert de point de départ aux débits d'écriture et
+/// à l'usage CPU) si le module est compilé et activé.
+/// Évite le coût de `System::new_all()` (utilisateurs, détails des processus…).
+pub fn system_refresh_kind(enabled: &EnabledMetrics) -> sysinfo::RefreshKind {
+    use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind};
+
+    let kind = RefreshKind::nothing()
+        .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+        .with_memory(MemoryRefreshKind::nothing().with_ram().with_swap());
+
+    if cfg!(feature = "processes") && enabled.processes {
+        kind.with_processes(
+            sysinfo::ProcessRefreshKind::nothing()
+                .with_tasks()
+                .with_cpu()
+                .with_memory()
+                .with_disk_usage(),
+        )
+    } else {
+        kind
+    }
+}
+
+/// Utilisation CPU (%) selon la source choisie. Sans cœur listé, la moyenne retombe
+/// sur la valeur globale.
+pub fn cpu_usage(sys: &sysinfo::System, source: CpuSource) -> f32 {
+    let per_core = per_core_usage(sys);
+    match source {
+        CpuSource::CoreAverage if !per_core.is_empty() => {
+            per_core.iter().sum::<f32>() / per_core.len() as f32
+        }
+        _ => sys.global_cpu_usage(),
+    }
+}
+
+/// Mémoire (utilisée, disponible, totale) en Mo ; sysinfo la compte en octets.
+pub fn memory_usage(sys: &sysinfo::System) -> (u64, u64, u64) {
+    (
+        sys.used_memory() / 1_048_576,
+        sys.available_memory() / 1_048_576,
+        sys.total_memory() / 1_048_576,
+    )
+}
+
+/// Swap (utilisé, total) en Mo. Le total vaut 0 quand le swap est désactivé.
+pub fn swap_usage(sys: &sysinfo::System) -> (u64, u64) {
+    (sys.used_swap() / 1_048_576, sys.total_swap() / 1_048_576)
+}
+
+/// Part du swap utilisée (%), `None` quand le swap est désactivé.
+pub fn swap_percent(sys: &sysinfo::System) -> Option<f32> {
+    let (used, total) = swap_usage(sys);
+    (total > 0).then(|| used as f32 / total as f32 * 100.0)
+}
+
+/// Utilisation (%) de chaque cœur logique, dans l'ordre de `sys.cpus()`.
+/// Appeler `refresh_cpu_usage()` avant : la mesure se fait entre deux rafraîchissements,
+/// si bien que la première lecture après `System::new()` peut valoir 0.
+pub fn per_core_usage(sys: &sysinfo::System) -> Vec<f32> {
+    sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect()
+}
+
+/// Charge moyenne sur 1, 5 et 15 minutes : nombre moyen de processus prêts ou en
+/// cours d'exécution, à rapporter au nombre de cœurs.
+#[cfg(unix)]
+pub fn load_average() -> (f64, f64, f64) {
+    let load = sysinfo::System::load_average();
+    (load.one, load.five, load.fifteen)
+}
+
+#[cfg(not(unix))]
+pub fn load_average() -> (f64, f64, f64) {
+    (0.0, 0.0, 0.0)
+}
+
+/// Secondes écoulées depuis le démarrage du système.
+pub fn uptime_seconds() -> u64 {
+    sysinfo::System::uptime()
+}
+
+/// Heure de démarrage du système, en secondes depuis l'époque Unix.
+pub fn boot_time() -> u64 {
+    sysinfo::System::boot_time()
+}
+
+// ============================================================================
+// MODULE TEMPÉRATURE (optionnel)
+// ============================================================================
+#[cfg(feature = "temperature")]
+pub mod temperature {
+    use sysinfo::Components;
+
+    /// Température de chaque cœur (capteurs nommés « Core N »), triée par
+    /// numéro de cœur. Vide si seul un capteur global est exposé.
+    pub fn per_core_temperatures(components: &Components) -> Vec<(String, f32)> {
+        let mut cores: Vec<(usize, String, f32)> = components
+            .iter()
+            .filter_map(|component| {
+                let index = core_index(component.label())?;
+                let temperature = component.temperature()?;
+                Some((index, component.label().to_string(), temperature))
+            })
+            .collect();
+
+        cores.sort_by_key(|(index, _, _)| *index);
+        cores
+            .into_iter()
+            .map(|(_, label, temperature)| (label, temperature))
+            .collect()
+    }
+
+    /// Température du processeur en °C, lue sur le capteur global (libellé
+    /// contenant « CPU », « Package » ou « Tdie »). `None` sans capteur
+    /// correspondant, ce qui est courant en machine virtuelle.
+    pub fn cpu_temperature(components: &Components) -> Option<f32> {
+        components
+            .iter()
+            .filter(|component| {
+                let label = component.label();
+                ["CPU", "Package", "Tdie"].iter().any(|name| label.contains(name))
+            })
+            .filter_map(|component| component.temperature())
+            .reduce(f32::max)
+    }
+
+    // Numéro N d'un libellé contenant « Core N » (ex. « coretemp Core 3 »)
+    fn core_index(label: &str) -> Option<usize> {
+        let words: Vec<&str> = label.split_whitespace().collect();
+        words
+            .windows(2)
+            .find(|pair| pair[0].eq_ignore_ascii_case("core"))
+            .and_then(|pair| pair[1].parse().ok())
+    }
+}
+
+/// Température la plus élevée parmi les cœurs.
+pub fn hottest_core(temperatures: &[(String, f32)]) -> Option<f32> {
+    temperatures
+        .iter()
+        .map(|(_, temperature)| *temperature)
+        .reduce(f32::max)
+}
+
+#[cfg(feature = "temperature")]
+pub use temperature::{cpu_temperature, per_core_temperatures};
+
+#[cfg(not(feature = "temperature"))]
+pub fn per_core_temperatures(_components: &sysinfo::Components) -> Vec<(String, f32)> {
+    Vec::new()
+}
+
+#[cfg(not(feature = "temperature"))]
+pub fn cpu_temperature(_components: &sysinfo::Components) -> Option<f32> {
+    None
+}
+
+// ============================================================================
+// MODULE GPU (optionnel)
+// ============================================================================
+// NVML est chargée à l'exécution : la crate se compile sans CUDA, et sans pilote
+// NVIDIA l'initialisation échoue simplement.
+#[cfg(feature = "gpu")]
+pub mod gpu {
+    use nvml_wrapper::Nvml;
+    use std::sync::OnceLock;
+
+    // Chargement de la bibliothèque coûteux : tenté une seule fois
+    static NVML: OnceLock<Option<Nvml>> = OnceLock::new();
+
+    /// Utilisation du premier GPU NVIDIA et part de sa mémoire occupée, en %.
+    /// `None` sans GPU ni pilote.
+    pub fn gpu_usage() -> Option<(f32, f32)> {
+        let nvml = NVML.get_or_init(|| Nvml::init().ok()).as_ref()?;
+        let device = nvml.device_by_index(0).ok()?;
+        let utilization = device.utilization_rates().ok()?;
+        let memory = device.memory_info().ok()?;
+
+        let memory_percent = if memory.total > 0 {
+            memory.used as f32 / memory.total as f32 * 100.0
+        } else {
+            0.0
+        };
+        Some((utilization.gpu as f32, memory_percent))
+    }
+}
+
+#[cfg(feature = "gpu")]
+pub use gpu::gpu_usage;
+
+#[cfg(not(feature = "gpu"))]
+pub fn gpu_usage() -> Option<(f32, f32)> {
+    None
+}
+
+// ============================================================================
+// INSTANTANÉ
+// ============================================================================
+// Relevé ponctuel de toutes les métriques, sérialisable en JSON (`--json`).
+// Les champs des modules désactivés valent `None`.
+pub mod snapshot {
+    use super::{get_battery_info, memory_available, Config, DiskUsage};
+    use serde::{Deserialize, Serialize};
+    use std::time::{Duration, Instant};
+    use sysinfo::System;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct Snapshot {
+        pub cpu: f32,
+        pub used_mem_mb: u64,
+        pub total_mem_mb: u64,
+        pub down_mbps: Option<f32>,
+        pub up_mbps: Option<f32>,
+        // Octets échangés depuis le démarrage de la machine
+        pub rx_total_bytes: Option<u64>,
+        pub tx_total_bytes: Option<u64>,
+        pub battery_percent: Option<f32>,
+        pub battery_charging: Option<bool>,
+        pub disk: Option<DiskUsage>,
+        pub process_count: Option<usize>,
+        pub thread_count: Option<usize>,
+        // Température du cœur le plus chaud (°C)
+        pub cpu_temperature: Option<f32>,
+        // Part du swap utilisée, `None` quand il est désactivé
+        pub swap_percent: Option<f32>,
+        // Utilisation du premier GPU NVIDIA (feature `gpu`)
+        pub gpu_percent: Option<f32>,
+    }
+
+    impl Snapshot {
+        pub fn ram_percent(&self) -> f32 {
+            if memory_available(self.total_mem_mb) {
+                (self.used_mem_mb as f32 / self.total_mem_mb as f32) * 100.0
+            } else {
+                0.0
+            }
+        }
 
-#[cfg(feature = "disk")]
-use sysinfo::Disks;
+        /// JSON de `--json` : les champs, suivis de la part de RAM utilisée pour les
+        /// scripts (ignorée à la relecture par `--remote`).
+        pub fn to_json(&self) -> serde_json::Result<String> {
+            #[derive(Serialize)]
+            struct WithRamPercent<'a> {
+                #[serde(flatten)]
+                snapshot: &'a Snapshot,
+                ram_percent: f32,
+            }
+
+            serde_json::to_string(&WithRamPercent {
+                snapshot: self,
+                ram_percent: self.ram_percent(),
+            })
+        }
+    }
+
+    // Écart entre deux instantanés ; `None` si la métrique manque dans l'un des deux
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct SnapshotDelta {
+        // Points de pourcentage
+        pub cpu: f32,
+        pub ram_percent: f32,
+        pub used_mem_mb: i64,
+        // Octets échangés entre les deux relevés (différence des compteurs cumulés)
+        pub rx_bytes: Option<u64>,
+        pub tx_bytes: Option<u64>,
+        pub disk_used_gb: Option<f32>,
+        pub process_count: Option<i64>,
+    }
+
+    /// Différence `b - a`, `a` étant l'instantané le plus ancien.
+    pub fn snapshot_delta(a: &Snapshot, b: &Snapshot) -> SnapshotDelta {
+        // Un compteur réinitialisé entre les deux relevés donne 0 plutôt qu'un négatif
+        let transferred = |before: Option<u64>, after: Option<u64>| {
+            Some(after?.saturating_sub(before?))
+        };
+
+        SnapshotDelta {
+            cpu: b.cpu - a.cpu,
+            ram_percent: b.ram_percent() - a.ram_percent(),
+            used_mem_mb: b.used_mem_mb as i64 - a.used_mem_mb as i64,
+            rx_bytes: transferred(a.rx_total_bytes, b.rx_total_bytes),
+            tx_bytes: transferred(a.tx_total_bytes, b.tx_total_bytes),
+            disk_used_gb: a
+                .disk
+                .zip(b.disk)
+                .map(|(before, after)| after.used_gb - before.used_gb),
+            process_count: a
+                .process_count
+                .zip(b.process_count)
+                .map(|(before, after)| after as i64 - before as i64),
+        }
+    }
+
+    /// Conserve les sources sysinfo entre deux relevés : l'utilisation CPU et
+    /// les débits réseau sont calculés depuis le relevé précédent.
+    pub struct SnapshotCollector {
+        sys: System,
+        #[cfg(feature = "network")]
+        networks: sysinfo::Networks,
+        #[cfg(feature = "network")]
+        rate_tracker: super::RateTracker,
+        #[cfg(feature = "disk")]
+        disks: sysinfo::Disks,
+        #[cfg(feature = "temperature")]
+        components: sysinfo::Components,
+        last_gather: Instant,
+        timings: GatherTimings,
+    }
+
+    impl SnapshotCollector {
+        /// Sources chargées selon les modules activés dans la configuration
+        /// (`system_refresh_kind`).
+        pub fn new(enabled: &super::EnabledMetrics) -> Self {
+            let mut sys = System::new_with_specifics(super::system_refresh_kind(enabled));
+            sys.refresh_cpu_usage();
+
+            #[cfg(feature = "network")]
+            let networks = sysinfo::Networks::new_with_refreshed_list();
+            #[cfg(feature = "network")]
+            let mut rate_tracker = super::RateTracker::new();
+            #[cfg(feature = "network")]
+            rate_tracker.update(&networks);
+
+            Self {
+                sys,
+                #[cfg(feature = "network")]
+                networks,
+                #[cfg(feature = "network")]
+                rate_tracker,
+                #[cfg(feature = "disk")]
+                disks: sysinfo::Disks::new_with_refreshed_list(),
+                #[cfg(feature = "temperature")]
+                components: sysinfo::Components::new_with_refreshed_list(),
+                last_gather: Instant::now(),
+                timings: GatherTimings::default(),
+            }
+        }
+
+        pub fn gather_snapshot(&mut self, config: &Config) -> Snapshot {
+            let now = Instant::now();
+            #[cfg(feature = "network")]
+            let elapsed_secs = now.duration_since(self.last_gather).as_secs_f32().max(0.001);
+            self.last_gather = now;
+
+            self.timings = GatherTimings::default();
+            let mut lap_start = Instant::now();
+
+            self.sys.refresh_cpu_usage();
+            self.timings.cpu = lap(&mut lap_start);
+            self.sys.refresh_memory();
+            self.timings.memory = lap(&mut lap_start);
+
+            let (used_mem_mb, _, total_mem_mb) = super::memory_usage(&self.sys);
+            let mut snapshot = Snapshot {
+                cpu: super::cpu_usage(&self.sys, config.cpu_source),
+                used_mem_mb,
+                total_mem_mb,
+                swap_percent: super::swap_percent(&self.sys),
+                gpu_percent: super::gpu_usage().map(|(utilization, _)| utilization),
+                ..Snapshot::default()
+            };
+
+            #[cfg(feature = "network")]
+            if config.enabled.network {
+                self.networks.refresh(true);
+                let (delta_rx, delta_tx) = self
+                    .rate_tracker
+                    .update_for(&self.networks, config.interfaces.as_deref());
+                snapshot.down_mbps = Some(delta_rx as f32 * 8.0 / 1_000_000.0 / elapsed_secs);
+                snapshot.up_mbps = Some(delta_tx as f32 * 8.0 / 1_000_000.0 / elapsed_secs);
+
+                let (rx_total, tx_total) =
+                    super::network_total_bytes(&self.networks, config.interfaces.as_deref());
+                snapshot.rx_total_bytes = Some(rx_total);
+                snapshot.tx_total_bytes = Some(tx_total);
+                self.timings.network = lap(&mut lap_start);
+            }
+
+            if cfg!(feature = "battery") && config.enabled.battery {
+                let (percent, charging) = get_battery_info();
+                snapshot.battery_percent = Some(percent);
+                snapshot.battery_charging = Some(charging);
+                self.timings.battery = lap(&mut lap_start);
+            }
+
+            #[cfg(feature = "disk")]
+            if config.enabled.disk {
+                self.disks.refresh(true);
+                snapshot.disk = Some(super::scoped_disk_usage(&self.disks, config.disk_scope));
+                self.timings.disk = lap(&mut lap_start);
+            }
+
+            #[cfg(feature = "processes")]
+            if config.enabled.processes {
+                super::refresh_processes(&mut self.sys);
+                let (processes, threads) = super::process_counts(&self.sys);
+                snapshot.process_count = Some(processes);
+                snapshot.thread_count = Some(threads);
+                self.timings.processes = lap(&mut lap_start);
+            }
+
+            #[cfg(feature = "temperature")]
+            {
+                self.components.refresh(false);
+                snapshot.cpu_temperature =
+                    super::hottest_core(&super::per_core_temperatures(&self.components));
+                self.timings.temperature = lap(&mut lap_start);
+            }
+
+            snapshot
+        }
+
+        /// Durée de chaque sous-système lors du dernier `gather_snapshot`.
+        pub fn last_timings(&self) -> &GatherTimings {
+            &self.timings
+        }
+    }
+
+    // Temps écoulé depuis `start`, qui est remis à l'instant présent
+    fn lap(start: &mut Instant) -> Duration {
+        let elapsed = start.elapsed();
+        *start = Instant::now();
+        elapsed
+    }
+
+    // Durées de rafraîchissement par sous-système (zéro si désactivé)
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct GatherTimings {
+        pub cpu: Duration,
+        pub memory: Duration,
+        pub network: Duration,
+        pub battery: Duration,
+        pub disk: Duration,
+        pub processes: Duration,
+        pub temperature: Duration,
+    }
+
+    impl GatherTimings {
+        pub fn entries(&self) -> [(&'static str, Duration); 7] {
+            [
+                ("cpu", self.cpu),
+                ("mémoire", self.memory),
+                ("réseau", self.network),
+                ("batterie", self.battery),
+                ("disque", self.disk),
+                ("processus", self.processes),
+                ("température", self.temperature),
+            ]
+        }
+
+        pub fn total(&self) -> Duration {
+            self.entries().iter().map(|(_, duration)| *duration).sum()
+        }
+
+        pub fn add(&mut self, other: &GatherTimings) {
+            self.cpu += other.cpu;
+            self.memory += other.memory;
+            self.network += other.network;
+            self.battery += other.battery;
+            self.disk += other.disk;
+            self.processes += other.processes;
+            self.temperature += other.temperature;
+        }
+    }
+
+    impl Default for SnapshotCollector {
+        fn default() -> Self {
+            Self::new(&super::EnabledMetrics::default())
+        }
+    }
+}
+
+pub use snapshot::{snapshot_delta, GatherTimings, Snapshot, SnapshotCollector, SnapshotDelta};
 
 // ============================================================================
-// MODULE BATTERIE (optionnel)
+// REGISTRE DES MÉTRIQUES
 // ============================================================================
-#[cfg(feature = "battery")]
-pub mod battery {
-    pub fn get_battery_info() -> (f32, bool) {
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
-            
-            if let Ok(output) = Command::new("pmset")
-                .arg("-g")
-                .arg("batt")
-                .output()
-            {
-                if let Ok(stdout) = String::from_utf8(output.stdout) {
-                    for line in stdout.lines() {
-                        if line.contains("InternalBattery") && line.contains("%") {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            for part in parts {
-                                if part.ends_with("%;") || part.ends_with('%') {
-                                    let clean = part.trim_end_matches(';').trim_end_matches('%');
-                                    if let Ok(percent) = clean.parse::<f32>() {
-                                        let charging = line.contains("charging") && !line.contains("discharging");
-                                        let ac_power = stdout.contains("AC Power");
-                                        return (percent, charging || ac_power);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+// Métriques graphables par leur nom (clé `charts` de la configuration). Une
+// nouvelle métrique s'ajoute ici sans toucher à la vue.
+pub mod metrics {
+    use super::Snapshot;
+
+    pub struct MetricDef {
+        pub name: &'static str,
+        pub label: &'static str,
+        pub unit: &'static str,
+        // Haut fixe du graphique, ou `None` pour suivre le maximum affiché
+        pub max_value: Option<f32>,
+        // `None` si la métrique n'est pas disponible (module désactivé, hôte distant…)
+        pub extract: fn(&Snapshot) -> Option<f32>,
+    }
+
+    pub const METRICS: &[MetricDef] = &[
+        MetricDef {
+            name: "cpu",
+            label: "CPU",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| Some(snapshot.cpu),
+        },
+        MetricDef {
+            name: "ram",
+            label: "RAM",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| Some(snapshot.ram_percent()),
+        },
+        MetricDef {
+            name: "down_mbps",
+            label: "Réception",
+            unit: "Mbps",
+            max_value: None,
+            extract: |snapshot| snapshot.down_mbps,
+        },
+        MetricDef {
+            name: "up_mbps",
+            label: "Émission",
+            unit: "Mbps",
+            max_value: None,
+            extract: |snapshot| snapshot.up_mbps,
+        },
+        MetricDef {
+            name: "battery",
+            label: "Batterie",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| snapshot.battery_percent,
+        },
+        MetricDef {
+            name: "disk",
+            label: "Stockage",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| snapshot.disk.map(|disk| disk.percent),
+        },
+        MetricDef {
+            name: "processes",
+            label: "Processus",
+            unit: "",
+            max_value: None,
+            extract: |snapshot| snapshot.process_count.map(|count| count as f32),
+        },
+        MetricDef {
+            name: "threads",
+            label: "Threads",
+            unit: "",
+            max_value: None,
+            extract: |snapshot| snapshot.thread_count.map(|count| count as f32),
+        },
+        MetricDef {
+            name: "cpu_temperature",
+            label: "Température CPU",
+            unit: "°C",
+            max_value: None,
+            extract: |snapshot| snapshot.cpu_temperature,
+        },
+        MetricDef {
+            name: "swap",
+            label: "Swap",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| snapshot.swap_percent,
+        },
+        MetricDef {
+            name: "gpu0_util",
+            label: "GPU 0",
+            unit: "%",
+            max_value: Some(100.0),
+            extract: |snapshot| snapshot.gpu_percent,
+        },
+    ];
+
+    pub fn find_metric(name: &str) -> Option<&'static MetricDef> {
+        METRICS.iter().find(|metric| metric.name == name)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn registry_names_are_unique() {
+            for (index, metric) in METRICS.iter().enumerate() {
+                assert!(METRICS[..index].iter().all(|other| other.name != metric.name));
             }
-            
-            (100.0, false)
         }
-        
-        #[cfg(not(target_os = "macos"))]
-        {
-            (100.0, false)
+
+        #[test]
+        fn swap_and_gpu_are_extracted_from_the_snapshot() {
+            let snapshot = Snapshot {
+                swap_percent: Some(25.0),
+                gpu_percent: Some(80.0),
+                ..Snapshot::default()
+            };
+            let extract = |name| (find_metric(name).unwrap().extract)(&snapshot);
+            assert_eq!(extract("swap"), Some(25.0));
+            assert_eq!(extract("gpu0_util"), Some(80.0));
+            assert_eq!(extract("battery"), None);
         }
     }
 }
 
-#[cfg(feature = "battery")]
-pub use battery::get_battery_info;
+pub use metrics::{find_metric, MetricDef, METRICS};
 
-#[cfg(not(feature = "battery"))]
-pub fn get_battery_info() -> (f32, bool) {
-    (100.0, false)
+// ============================================================================
+// ICÔNE DE LA BARRE DE MENU
+// ============================================================================
+// Couleur de l'icône du widget selon la charge. Les mêmes seuils servent pour
+// les pourcentages et pour la température (en °C).
+pub mod tray {
+    use super::{Snapshot, TrayColorSource};
+
+    const ELEVATED_THRESHOLD: f32 = 60.0;
+    const CRITICAL_THRESHOLD: f32 = 85.0;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum LoadLevel {
+        Normal,
+        Elevated,
+        Critical,
+    }
+
+    impl LoadLevel {
+        pub fn from_value(value: f32) -> Self {
+            if value >= CRITICAL_THRESHOLD {
+                LoadLevel::Critical
+            } else if value >= ELEVATED_THRESHOLD {
+                LoadLevel::Elevated
+            } else {
+                LoadLevel::Normal
+            }
+        }
+
+        pub fn rgb(self) -> [u8; 3] {
+            match self {
+                LoadLevel::Normal => [0x10, 0xb9, 0x81],
+                LoadLevel::Elevated => [0xf5, 0x9e, 0x0b],
+                LoadLevel::Critical => [0xef, 0x44, 0x44],
+            }
+        }
+    }
+
+    /// Valeur suivie par l'icône. Si la source choisie n'est pas disponible
+    /// (feature désactivée, pas de capteur), l'icône suit le CPU.
+    pub fn tray_color_value(snapshot: &Snapshot, source: TrayColorSource) -> f32 {
+        match source {
+            TrayColorSource::Cpu => snapshot.cpu,
+            TrayColorSource::Ram => snapshot.ram_percent(),
+            TrayColorSource::Hottest => snapshot
+                .cpu
+                .max(snapshot.ram_percent())
+                .max(snapshot.disk.map_or(0.0, |disk| disk.percent)),
+            TrayColorSource::Temperature => snapshot.cpu_temperature.unwrap_or(snapshot.cpu),
+        }
+    }
+
+    /// Pixels RGBA d'une icône carrée : un disque de la couleur du niveau.
+    pub fn level_icon_rgba(level: LoadLevel, size: u32) -> Vec<u8> {
+        let [r, g, b] = level.rgb();
+        let center = size as f32 / 2.0;
+        let radius = center - 1.0;
+
+        (0..size * size)
+            .flat_map(|index| {
+                let dx = (index % size) as f32 + 0.5 - center;
+                let dy = (index / size) as f32 + 0.5 - center;
+                let alpha = if dx * dx + dy * dy <= radius * radius { 0xff } else { 0 };
+                [r, g, b, alpha]
+            })
+            .collect()
+    }
 }
 
+pub use tray::{level_icon_rgba, tray_color_value, LoadLevel};
+
 // ============================================================================
-// MODULE RÉSEAU (optionnel)
+// HÔTE DISTANT
 // ============================================================================
-#[cfg(feature = "network")]
-pub mod network {
-    use sysinfo::Networks;
+// Surveillance d'une autre machine : `monitor_app --json` y est exécuté via SSH
+// et l'instantané JSON renvoyé est affiché localement.
+pub mod remote {
+    use super::Snapshot;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
 
-    pub fn network_deltas(networks: &Networks) -> (u64, u64) {
-        let mut rx = 0;
-        let mut tx = 0;
+    const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+    const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
 
-        for (_name, data) in networks {
-            rx += data.received();
-            tx += data.transmitted();
+    /// Exécute `monitor_app --json` sur `target` (`user@host`). Bloquant, mais borné :
+    /// une connexion qui ne répond plus est coupée par ssh après une dizaine de secondes.
+    pub fn fetch_remote_snapshot(target: &str) -> Result<Snapshot, String> {
+        let output = Command::new("ssh")
+            .args([
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ConnectTimeout=5",
+                // Sans réponse à deux sondes espacées de 5 s, la session est fermée
+                "-o",
+                "ServerAliveInterval=5",
+                "-o",
+                "ServerAliveCountMax=2",
+                // `target` ne peut pas être lu comme une option de ssh
+                "--",
+                target,
+                "monitor_app",
+                "--json",
+            ])
+            .output()
+            .map_err(|err| format!("ssh introuvable : {}", err))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
         }
 
-        (rx, tx)
+        serde_json::from_slice(&output.stdout).map_err(|err| format!("réponse invalide : {}", err))
     }
 
-    pub fn network_totals(networks: &Networks) -> (f32, f32) {
-        let mut rx = 0_u64;
-        let mut tx = 0_u64;
+    // État de la connexion, avec un délai de reconnexion qui double à chaque échec
+    #[derive(Debug, Clone)]
+    pub struct RemoteHost {
+        pub target: String,
+        pub connected: bool,
+        pub pending: bool,
+        retry_delay: Duration,
+        next_attempt: Instant,
+    }
 
-        for (_name, data) in networks {
-            rx += data.total_received();
-            tx += data.total_transmitted();
+    impl RemoteHost {
+        pub fn new(target: String) -> Self {
+            Self {
+                target,
+                connected: false,
+                pending: false,
+                retry_delay: INITIAL_RETRY_DELAY,
+                next_attempt: Instant::now(),
+            }
         }
 
-        (
-            rx as f32 / 1_073_741_824.0,
-            tx as f32 / 1_073_741_824.0,
-        )
+        pub fn should_fetch(&self, now: Instant) -> bool {
+            !self.pending && now >= self.next_attempt
+        }
+
+        pub fn on_success(&mut self) {
+            self.pending = false;
+            self.connected = true;
+            self.retry_delay = INITIAL_RETRY_DELAY;
+        }
+
+        pub fn on_failure(&mut self, now: Instant) {
+            self.pending = false;
+            self.connected = false;
+            self.next_attempt = now + self.retry_delay;
+            self.retry_delay = (self.retry_delay * 2).min(MAX_RETRY_DELAY);
+        }
     }
 }
 
-#[cfg(feature = "network")]
-pub use network::{network_deltas, network_totals};
+pub use remote::{fetch_remote_snapshot, RemoteHost};
 
-#[cfg(not(feature = "network"))]
-pub fn network_deltas(_networks: &sysinfo::Networks) -> (u64, u64) {
-    (0, 0)
+// ============================================================================
+// ALERTES (optionnelles)
+// ============================================================================
+// Notifications de bureau natives (libnotify sous Linux, centre de notifications
+// sous macOS, toasts sous Windows).
+#[cfg(feature = "alerts")]
+pub mod alerts {
+    /// Signale un CPU resté à `percent` ou plus pendant `ticks` relevés.
+    pub fn notify_cpu(percent: f32, ticks: u32) -> Result<(), String> {
+        notify_rust::Notification::new()
+            .summary("System Monitor")
+            .body(&format!("CPU à {:.0}% depuis {} relevés", percent, ticks))
+            .show()
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
 }
 
-#[cfg(not(feature = "network"))]
-pub fn network_totals(_networks: &sysinfo::Networks) -> (f32, f32) {
-    (0.0, 0.0)
+#[cfg(feature = "alerts")]
+pub use alerts::notify_cpu;
+
+#[cfg(not(feature = "alerts"))]
+pub fn notify_cpu(_percent: f32, _ticks: u32) -> Result<(), String> {
+    Ok(())
 }
 
 // ============================================================================
-// MODULE DISQUE (optionnel)
+// INTERFACE DBUS (optionnelle, Linux)
 // ============================================================================
-#[cfg(feature = "disk")]
-pub mod disk {
-    use sysinfo::Disks;
+// Expose l'instantané courant sur le bus de session pour les widgets de bureau
+// (GNOME, KDE) : méthode `GetSnapshot` et signal `SnapshotChanged` à chaque tick.
+// Les champs sont envoyés dans un dictionnaire `a{sv}`, sans les mesures absentes.
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub mod dbus {
+    use super::Snapshot;
+    use std::collections::HashMap;
+    use zbus::blocking::{connection, Connection};
+    use zbus::object_server::SignalEmitter;
+    use zbus::zvariant::Value;
 
-    pub fn get_disk_usage(disks: &Disks) -> (f32, u64, u64) {
-        let mut total_space = 0_u64;
-        let mut used_space = 0_u64;
+    pub const BUS_NAME: &str = "org.bxota.SystemMonitor";
+    const OBJECT_PATH: &str = "/org/bxota/SystemMonitor";
 
-        for disk in disks {
-            total_space += disk.total_space();
-            used_space += disk.total_space() - disk.available_space();
+    pub type SnapshotFields = HashMap<String, Value<'static>>;
+
+    pub fn snapshot_fields(snapshot: &Snapshot) -> SnapshotFields {
+        let mut fields = HashMap::new();
+        fields.insert("cpu".to_string(), Value::from(snapshot.cpu));
+        fields.insert("used_mem_mb".to_string(), Value::from(snapshot.used_mem_mb));
+        fields.insert("total_mem_mb".to_string(), Value::from(snapshot.total_mem_mb));
+
+        let optional = [
+            ("down_mbps", snapshot.down_mbps.map(Value::from)),
+            ("up_mbps", snapshot.up_mbps.map(Value::from)),
+            ("rx_total_bytes", snapshot.rx_total_bytes.map(Value::from)),
+            ("tx_total_bytes", snapshot.tx_total_bytes.map(Value::from)),
+            ("battery_percent", snapshot.battery_percent.map(Value::from)),
+            ("battery_charging", snapshot.battery_charging.map(Value::from)),
+            ("disk_percent", snapshot.disk.map(|disk| Value::from(disk.percent))),
+            ("disk_used_gb", snapshot.disk.map(|disk| Value::from(disk.used_gb))),
+            ("disk_total_gb", snapshot.disk.map(|disk| Value::from(disk.total_gb))),
+            ("process_count", snapshot.process_count.map(|count| Value::from(count as u64))),
+            ("thread_count", snapshot.thread_count.map(|count| Value::from(count as u64))),
+            ("cpu_temperature", snapshot.cpu_temperature.map(Value::from)),
+            ("swap_percent", snapshot.swap_percent.map(Value::from)),
+            ("gpu_percent", snapshot.gpu_percent.map(Value::from)),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = value {
+                fields.insert(name.to_string(), value);
+            }
         }
 
-        let percent = if total_space > 0 {
-            (used_space as f32 / total_space as f32) * 100.0
-        } else {
-            0.0
+        fields
+    }
+
+    #[derive(Default)]
+    struct SnapshotInterface {
+        snapshot: Snapshot,
+    }
+
+    #[zbus::interface(name = "org.bxota.SystemMonitor")]
+    impl SnapshotInterface {
+        fn get_snapshot(&self) -> SnapshotFields {
+            snapshot_fields(&self.snapshot)
+        }
+
+        #[zbus(signal)]
+        async fn snapshot_changed(emitter: &SignalEmitter<'_>, snapshot: SnapshotFields) -> zbus::Result<()>;
+    }
+
+    pub struct DbusPublisher {
+        connection: Connection,
+    }
+
+    impl DbusPublisher {
+        /// Réserve le nom sur le bus de session. Échoue si le bus est injoignable
+        /// ou le nom déjà pris.
+        pub fn start() -> Result<Self, String> {
+            connection::Builder::session()
+                .and_then(|builder| builder.name(BUS_NAME))
+                .and_then(|builder| builder.serve_at(OBJECT_PATH, SnapshotInterface::default()))
+                .and_then(|builder| builder.build())
+                .map(|connection| Self { connection })
+                .map_err(|err| format!("{} : {}", BUS_NAME, err))
+        }
+
+        pub fn publish(&self, snapshot: &Snapshot) -> Result<(), String> {
+            if let Ok(interface) = self
+                .connection
+                .object_server()
+                .interface::<_, SnapshotInterface>(OBJECT_PATH)
+            {
+                interface.get_mut().snapshot = snapshot.clone();
+            }
+
+            self.connection
+                .emit_signal(
+                    None::<&str>,
+                    OBJECT_PATH,
+                    BUS_NAME,
+                    "SnapshotChanged",
+                    &snapshot_fields(snapshot),
+                )
+                .map_err(|err| format!("signal non émis : {}", err))
+        }
+    }
+}
+
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub use dbus::DbusPublisher;
+
+// ============================================================================
+// EXPORT PROMETHEUS (optionnel)
+// ============================================================================
+#[cfg(feature = "metrics")]
+pub mod prometheus {
+    use super::Snapshot;
+    use std::fmt::Write;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    /// Instantané au format texte de Prometheus : une jauge (ou un compteur pour les
+    /// cumuls) par mesure disponible, les mesures absentes étant omises.
+    pub fn prometheus_text(snapshot: &Snapshot) -> String {
+        let gb_to_bytes = |gb: f32| f64::from(gb) * 1_073_741_824.0;
+        let metrics = [
+            (
+                "system_cpu_percent",
+                "gauge",
+                "Utilisation CPU (%)",
+                Some(f64::from(snapshot.cpu)),
+            ),
+            (
+                "system_ram_percent",
+                "gauge",
+                "Mémoire utilisée (%)",
+                Some(f64::from(snapshot.ram_percent())),
+            ),
+            (
+                "system_swap_percent",
+                "gauge",
+                "Swap utilisé (%)",
+                snapshot.swap_percent.map(f64::from),
+            ),
+            (
+                "system_gpu_percent",
+                "gauge",
+                "Utilisation du GPU (%)",
+                snapshot.gpu_percent.map(f64::from),
+            ),
+            (
+                "system_net_rx_mbps",
+                "gauge",
+                "Débit reçu (Mbps)",
+                snapshot.down_mbps.map(f64::from),
+            ),
+            (
+                "system_net_tx_mbps",
+                "gauge",
+                "Débit émis (Mbps)",
+                snapshot.up_mbps.map(f64::from),
+            ),
+            (
+                "system_net_rx_bytes_total",
+                "counter",
+                "Octets reçus depuis le démarrage",
+                snapshot.rx_total_bytes.map(|bytes| bytes as f64),
+            ),
+            (
+                "system_net_tx_bytes_total",
+                "counter",
+                "Octets émis depuis le démarrage",
+                snapshot.tx_total_bytes.map(|bytes| bytes as f64),
+            ),
+            (
+                "system_battery_percent",
+                "gauge",
+                "Niveau de batterie (%)",
+                snapshot.battery_percent.map(f64::from),
+            ),
+            (
+                "system_battery_charging",
+                "gauge",
+                "Batterie en charge (1) ou non (0)",
+                snapshot
+                    .battery_charging
+                    .map(|charging| f64::from(u8::from(charging))),
+            ),
+            (
+                "system_disk_percent",
+                "gauge",
+                "Stockage utilisé (%)",
+                snapshot.disk.map(|disk| f64::from(disk.percent)),
+            ),
+            (
+                "system_disk_used_bytes",
+                "gauge",
+                "Stockage utilisé (octets)",
+                snapshot.disk.map(|disk| gb_to_bytes(disk.used_gb)),
+            ),
+            (
+                "system_disk_total_bytes",
+                "gauge",
+                "Capacité de stockage (octets)",
+                snapshot.disk.map(|disk| gb_to_bytes(disk.total_gb)),
+            ),
+            (
+                "system_processes",
+                "gauge",
+                "Nombre de processus",
+                snapshot.process_count.map(|count| count as f64),
+            ),
+            (
+                "system_threads",
+                "gauge",
+                "Nombre de threads",
+                snapshot.thread_count.map(|count| count as f64),
+            ),
+            (
+                "system_cpu_temperature_celsius",
+                "gauge",
+                "Température du cœur le plus chaud (°C)",
+                snapshot.cpu_temperature.map(f64::from),
+            ),
+        ];
+
+        let mut text = String::new();
+        for (name, kind, help, value) in metrics {
+            if let Some(value) = value {
+                let _ = writeln!(text, "# HELP {} {}", name, help);
+                let _ = writeln!(text, "# TYPE {} {}", name, kind);
+                let _ = writeln!(text, "{} {}", name, value);
+            }
+        }
+        text
+    }
+
+    /// Sert `/metrics` à l'adresse donnée, depuis un thread dédié, à partir du dernier
+    /// instantané publié.
+    pub struct MetricsServer {
+        // `None` jusqu'au premier relevé : aucune valeur factice n'est exposée
+        snapshot: Arc<Mutex<Option<Snapshot>>>,
+    }
+
+    impl MetricsServer {
+        /// N'écoute que sur l'adresse donnée (`127.0.0.1` par défaut côté application) :
+        /// les mesures sont servies sans authentification. Échoue si le port est déjà pris.
+        pub fn start(addr: SocketAddr) -> Result<Self, String> {
+            let server =
+                tiny_http::Server::http(addr).map_err(|err| format!("{} : {}", addr, err))?;
+            let snapshot = Arc::new(Mutex::new(None));
+
+            let shared = Arc::clone(&snapshot);
+            std::thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let text = (request.url() == "/metrics").then(|| {
+                        shared
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .as_ref()
+                            .map(prometheus_text)
+                    });
+                    let response = match text {
+                        Some(Some(text)) => {
+                            let mut response = tiny_http::Response::from_string(text);
+                            if let Ok(header) = tiny_http::Header::from_bytes(
+                                "Content-Type",
+                                "text/plain; version=0.0.4; charset=utf-8",
+                            ) {
+                                response.add_header(header);
+                            }
+                            response
+                        }
+                        // Pas encore de relevé : une erreur plutôt que des mesures à zéro
+                        Some(None) => tiny_http::Response::from_string("aucun relevé")
+                            .with_status_code(503),
+                        None => tiny_http::Response::from_string("introuvable").with_status_code(404),
+                    };
+                    let _ = request.respond(response);
+                }
+            });
+
+            Ok(Self { snapshot })
+        }
+
+        pub fn publish(&self, snapshot: &Snapshot) {
+            *self
+                .snapshot
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(snapshot.clone());
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn swap_and_gpu_are_exported_when_measured() {
+            let snapshot = Snapshot {
+                swap_percent: Some(25.0),
+                gpu_percent: Some(80.0),
+                ..Snapshot::default()
+            };
+            let text = prometheus_text(&snapshot);
+            assert!(text.contains("# TYPE system_swap_percent gauge\nsystem_swap_percent 25\n"));
+            assert!(text.contains("# TYPE system_gpu_percent gauge\nsystem_gpu_percent 80\n"));
+            assert!(text.contains("system_cpu_percent 0\n"));
+
+            // Mesures absentes : omises plutôt qu'exportées à zéro
+            let text = prometheus_text(&Snapshot::default());
+            assert!(!text.contains("system_swap_percent"));
+            assert!(!text.contains("system_gpu_percent"));
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub use prometheus::{prometheus_text, MetricsServer};
+
+// ============================================================================
+// COPIE D'UN GRAPHIQUE EN IMAGE (optionnelle)
+// ============================================================================
+// `iced::clipboard` ne transporte que du texte : l'image passe par arboard.
+#[cfg(feature = "clipboard-image")]
+pub mod chart_image {
+    use iced::Color;
+
+    const STROKE_WIDTH: isize = 2;
+
+    /// Rastérise une courbe en RGBA (`width` × `height` pixels) : fond uni, puis la
+    /// courbe de 0 à `max_value`, comme la `Sparkline` de l'interface.
+    pub fn rasterize_sparkline(
+        values: &[f32],
+        max_value: f32,
+        color: Color,
+        background: Color,
+        width: usize,
+        height: usize,
+    ) -> Vec<u8> {
+        let mut pixels = background.into_rgba8().repeat(width * height);
+        if values.len() < 2 || !max_value.is_finite() || max_value <= 0.0 || width < 2 {
+            return pixels;
+        }
+
+        let plot_height = height.saturating_sub(STROKE_WIDTH as usize) as f32;
+        let step_x = (width - 1) as f32 / (values.len() - 1) as f32;
+        let point = |i: usize, value: f32| {
+            let ratio = (value / max_value).clamp(0.0, 1.0);
+            (i as f32 * step_x, plot_height - ratio * plot_height)
+        };
+
+        let rgba = color.into_rgba8();
+        let mut plot = |x: f32, y: f32| {
+            // Pinceau carré de l'épaisseur du trait, rogné aux bords de l'image
+            for dy in 0..STROKE_WIDTH {
+                for dx in 0..STROKE_WIDTH {
+                    let (px, py) = (x.round() as isize + dx - 1, y.round() as isize + dy);
+                    if (0..width as isize).contains(&px) && (0..height as isize).contains(&py) {
+                        let offset = (py as usize * width + px as usize) * 4;
+                        pixels[offset..offset + 4].copy_from_slice(&rgba);
+                    }
+                }
+            }
         };
 
-        let total_gb = total_space / 1_073_741_824;
-        let used_gb = used_space / 1_073_741_824;
+        // Un segment touchant une valeur non finie n'est pas tracé : le trou reste visible
+        for (i, pair) in values.windows(2).enumerate() {
+            if !(pair[0].is_finite() && pair[1].is_finite()) {
+                continue;
+            }
+            let (x0, y0) = point(i, pair[0]);
+            let (x1, y1) = point(i + 1, pair[1]);
+            let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as usize;
+            for step in 0..=steps {
+                let t = step as f32 / steps as f32;
+                plot(x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+            }
+        }
+
+        pixels
+    }
+
+    /// Presse-papiers système pour les images. À garder tant que l'application
+    /// tourne : sous Linux, l'image n'est servie que tant qu'il existe.
+    pub struct ImageClipboard(arboard::Clipboard);
+
+    impl ImageClipboard {
+        pub fn new() -> Result<Self, String> {
+            arboard::Clipboard::new()
+                .map(Self)
+                .map_err(|err| format!("presse-papiers indisponible : {}", err))
+        }
 
-        (percent, used_gb, total_gb)
+        /// Copie une image RGBA de `width` × `height` pixels.
+        pub fn copy_rgba(&mut self, rgba: Vec<u8>, width: usize, height: usize) -> Result<(), String> {
+            self.0
+                .set_image(arboard::ImageData {
+                    width,
+                    height,
+                    bytes: rgba.into(),
+                })
+                .map_err(|err| format!("copie impossible : {}", err))
+        }
     }
 }
 
-#[cfg(feature = "disk")]
-pub use disk::get_disk_usage;
+#[cfg(feature = "clipboard-image")]
+pub use chart_image::{rasterize_sparkline, ImageClipboard};
 
-#[cfg(not(feature = "disk"))]
-pub fn get_disk_usage(_disks: &sysinfo::Disks) -> (f32, u64, u64) {
-    (0.0, 0, 0)
+// ============================================================================
+// DIAGNOSTIC
+// ============================================================================
+// Rapport texte joint aux tickets : valeurs brutes de sysinfo et des commandes
+// système, suivies des métriques calculées par les fonctions de la bibliothèque.
+pub mod diagnostics {
+    use std::fmt::Write;
+    use sysinfo::{Disks, Networks, System};
+
+    const MASK: &str = "<masqué>";
+
+    /// Construit le rapport. En mode `redacted`, le nom d'hôte et les noms
+    /// d'interfaces réseau sont masqués.
+    pub fn diagnostics_report(redacted: bool) -> String {
+        let mut sys = System::new_all();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+
+        let mut networks = Networks::new_with_refreshed_list();
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        networks.refresh(true);
+
+        let disks = Disks::new_with_refreshed_list();
+
+        let mut report = String::new();
+        // L'écriture dans une String ne peut pas échouer
+        let _ = write_report(&mut report, &sys, &networks, &disks, redacted);
+        report
+    }
+
+    fn write_report(
+        out: &mut String,
+        sys: &System,
+        networks: &Networks,
+        disks: &Disks,
+        redacted: bool,
+    ) -> std::fmt::Result {
+        writeln!(out, "# Diagnostic monitor_app {}", env!("CARGO_PKG_VERSION"))?;
+        writeln!(out)?;
+
+        writeln!(out, "## Système")?;
+        writeln!(out, "os: {}", System::long_os_version().unwrap_or_default())?;
+        writeln!(out, "noyau: {}", System::kernel_version().unwrap_or_default())?;
+        writeln!(out, "architecture: {}", System::cpu_arch())?;
+        let host_name = System::host_name().unwrap_or_default();
+        writeln!(out, "hôte: {}", if redacted { MASK } else { &host_name })?;
+        writeln!(out, "features: {}", compiled_features().join(", "))?;
+        writeln!(out)?;
+
+        writeln!(out, "## CPU")?;
+        writeln!(out, "global: {:.2} %", sys.global_cpu_usage())?;
+        for cpu in sys.cpus() {
+            writeln!(
+                out,
+                "{}: {:.2} % {} MHz ({})",
+                cpu.name(),
+                cpu.cpu_usage(),
+                cpu.frequency(),
+                cpu.brand()
+            )?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Mémoire (octets)")?;
+        writeln!(out, "total: {}", sys.total_memory())?;
+        writeln!(out, "utilisée: {}", sys.used_memory())?;
+        writeln!(out, "disponible: {}", sys.available_memory())?;
+        writeln!(out, "swap totale: {}", sys.total_swap())?;
+        writeln!(out, "swap utilisée: {}", sys.used_swap())?;
+        writeln!(out)?;
+
+        writeln!(out, "## Réseau (octets)")?;
+        let mut interfaces: Vec<_> = networks.iter().collect();
+        interfaces.sort_by(|a, b| a.0.cmp(b.0));
+        for (index, (name, data)) in interfaces.into_iter().enumerate() {
+            let name = if redacted { format!("if{}", index) } else { name.clone() };
+            writeln!(
+                out,
+                "{}: delta ↓{} ↑{} total ↓{} ↑{}",
+                name,
+                data.received(),
+                data.transmitted(),
+                data.total_received(),
+                data.total_transmitted()
+            )?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Disques (octets)")?;
+        for disk in disks {
+            writeln!(
+                out,
+                "{:?} sur {}: {:?} {:?} total {} disponible {} amovible {}",
+                disk.name(),
+                disk.mount_point().display(),
+                disk.file_system(),
+                disk.kind(),
+                disk.total_space(),
+                disk.available_space(),
+                disk.is_removable()
+            )?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Batterie (sortie brute)")?;
+        match super::raw_battery_output() {
+            Some(output) => writeln!(out, "{}", output.trim_end())?,
+            None => writeln!(out, "(aucune commande batterie sur cette plateforme)")?,
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Métriques calculées")?;
+        // Même source CPU que l'application, d'après la configuration de l'utilisateur
+        let cpu_source = super::Config::load().cpu_source;
+        writeln!(out, "CPU: {:.1} % (source: {:?})", super::cpu_usage(sys, cpu_source), cpu_source)?;
+        let (used_mem_mb, _, total_mem_mb) = super::memory_usage(sys);
+        if super::memory_available(total_mem_mb) {
+            writeln!(
+                out,
+                "RAM: {:.1} % ({} / {} Mo)",
+                used_mem_mb as f32 / total_mem_mb as f32 * 100.0,
+                used_mem_mb,
+                total_mem_mb
+            )?;
+        } else {
+            writeln!(out, "RAM: mémoire totale inconnue")?;
+        }
+        let (battery_percent, battery_charging) = super::get_battery_info();
+        writeln!(out, "batterie: {:.0} % en charge: {}", battery_percent, battery_charging)?;
+        let (rx_gib, tx_gib) = super::network_totals(networks);
+        writeln!(out, "réseau total: ↓{:.2} GiB ↑{:.2} GiB", rx_gib, tx_gib)?;
+        if let Some((ipv4, ipv6)) = super::ip_version_bytes() {
+            writeln!(out, "réseau IPv4/IPv6: {} / {} octets", ipv4, ipv6)?;
+        }
+        let (disk_percent, disk_used_gb, disk_total_gb) = super::get_disk_usage(disks);
+        writeln!(
+            out,
+            "stockage: {:.1} % ({} / {})",
+            disk_percent,
+            super::format_gb(disk_used_gb),
+            super::format_gb(disk_total_gb)
+        )?;
+        let (processes, threads) = super::process_counts(sys);
+        writeln!(out, "processus: {} threads: {}", processes, threads)?;
+
+        Ok(())
+    }
+
+    fn compiled_features() -> Vec<&'static str> {
+        [
+            ("battery", cfg!(feature = "battery")),
+            ("network", cfg!(feature = "network")),
+            ("disk", cfg!(feature = "disk")),
+            ("processes", cfg!(feature = "processes")),
+            ("netstat", cfg!(feature = "netstat")),
+            ("temperature", cfg!(feature = "temperature")),
+            ("dbus", cfg!(feature = "dbus")),
+            ("clipboard-image", cfg!(feature = "clipboard-image")),
+            ("metrics", cfg!(feature = "metrics")),
+            ("alerts", cfg!(feature = "alerts")),
+            ("gpu", cfg!(feature = "gpu")),
+            ("widget", cfg!(feature = "widget")),
+            ("minimal", cfg!(feature = "minimal")),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect()
+    }
 }
+
+pub use diagnostics::diagnostics_report;
@@ -1,74 +1,343 @@
-use iced::widget::canvas::{Canvas, Frame, Geometry, Path, Program, Stroke};
-use iced::widget::{button, column, container, progress_bar, row, text};
-use iced::{mouse, time, Border, Color, Element, Length, Pixels, Point, Rectangle, Renderer, Shadow, Subscription, Task, Theme};
-
+#[cfg(not(feature = "minimal"))]
+use iced::widget::canvas::{Cache, Canvas};
+use iced::widget::{button, column, container, progress_bar, row, text, tooltip};
+use iced::{keyboard, system, theme, time, window, Border, Color, Element, Length, Shadow, Subscription, Task, Theme};
+#[cfg(not(feature = "minimal"))]
+use iced::Pixels;
+
+use monitor_app::{
+    busy_core_count, contrast_color, cpu_usage, create_tab_button, diagnostics_report,
+    fetch_remote_snapshot, format_bytes, format_interval, load_average, memory_available,
+    memory_pressure, memory_usage, per_core_usage, snapshot_delta, swap_percent,
+    system_refresh_kind,
+    Config, EnabledMetrics, GatherTimings, Icon, IconSet, LoadLevel, Module, RemoteHost,
+    Snapshot, SnapshotCollector, SnapshotDelta, TabSize,
+};
+// Historiques et graphiques, absents du profil `minimal`
+#[cfg(not(feature = "minimal"))]
+use monitor_app::{
+    find_metric, history_csv, smoothed_latest, std_dev, BaselineComparator, BaselineMode,
+    CorrelationChart, History, MetricDef, PointBuffer, SavedHistory, Smoothing, Sparkline,
+};
 #[cfg(feature = "battery")]
-use monitor_app::get_battery_info;
+use monitor_app::{get_battery_info, read_battery_info, threshold_color};
+#[cfg(feature = "netstat")]
+use monitor_app::ip_version_bytes;
+#[cfg(feature = "gpu")]
+use monitor_app::gpu_usage;
+#[cfg(feature = "processes")]
+use monitor_app::{
+    process_churn, process_counts, process_pids, refresh_processes, top_processes_by_disk_write,
+};
+#[cfg(feature = "temperature")]
+use monitor_app::per_core_temperatures;
 #[cfg(feature = "disk")]
-use monitor_app::get_disk_usage;
+use monitor_app::{disk_io_deltas, format_gb, scoped_disk_usage, DiskScope, DiskUsage};
 #[cfg(feature = "network")]
-use monitor_app::{network_deltas, network_totals};
+use monitor_app::{
+    classify_interface, dominant_interface, format_rate, interface_deltas, is_allowed_interface,
+    per_interface_deltas, net_utilization, network_total_bytes,
+    top_contributor, top_interfaces,
+    RateTracker,
+};
+#[cfg(all(feature = "network", not(feature = "minimal")))]
+use monitor_app::RateUnit;
+
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+use monitor_app::DbusPublisher;
+#[cfg(feature = "metrics")]
+use monitor_app::MetricsServer;
+#[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+use monitor_app::{rasterize_sparkline, smooth_with_gaps, ImageClipboard};
 
 #[cfg(feature = "disk")]
 use sysinfo::Disks;
 #[cfg(feature = "network")]
 use sysinfo::Networks;
 use sysinfo::System;
-
-use std::time::Duration;
+#[cfg(feature = "temperature")]
+use sysinfo::Components;
+
+#[cfg(all(feature = "network", not(feature = "minimal")))]
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+#[cfg(feature = "processes")]
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tab {
     System,
     Network,
     Power,
+    Settings,
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     Tick,
     TabSelected(Tab),
+    SetInterval(u64),
+    #[cfg(not(feature = "minimal"))]
+    SetHistoryLen(usize),
+    #[cfg(not(feature = "minimal"))]
+    ToggleReduceMotion,
+    #[cfg(not(feature = "minimal"))]
+    ToggleChartBaseline,
+    ToggleModule(Module),
+    SetIconSet(IconSet),
+    #[cfg(feature = "disk")]
+    ToggleDiskScope,
+    TogglePause,
+    LocalRefreshed(Result<LocalReadings, String>),
+    RemoteSnapshot(Result<Snapshot, String>),
+    #[cfg(feature = "network")]
+    ResetNetworkPeak,
+    MarkSnapshot,
+    CompareSnapshot,
+    #[cfg(not(feature = "minimal"))]
+    LearnBaseline,
+    ResetAll,
+    #[cfg(not(feature = "minimal"))]
+    ExportHistory(PathBuf),
+    #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+    CopyChartImage,
+    SystemThemeChanged(theme::Mode),
+    CloseRequested,
 }
 
 struct State {
+    config: Config,
     cpu: f32,
+    // Utilisation de chaque cœur logique, en %
+    core_usages: Vec<f32>,
+    // Charge moyenne sur 1, 5 et 15 minutes (Unix uniquement)
+    load_average: (f64, f64, f64),
     used_mem_mb: u64,
+    // Mémoire disponible (libre + cache récupérable), pour la pression mémoire
+    available_mem_mb: u64,
     total_mem_mb: u64,
+    // `None` quand le swap est désactivé
+    swap_percent: Option<f32>,
+    // Utilisation du premier GPU NVIDIA, `None` sans GPU ni pilote
+    #[cfg(feature = "gpu")]
+    gpu_percent: Option<f32>,
     current_tab: Tab,
-    cpu_history: Vec<f32>,
-    ram_history: Vec<f32>,
+    // Historiques et courbes, absents du profil `minimal`
+    #[cfg(not(feature = "minimal"))]
+    charts: Charts,
+    ticks: u64,
+    // Instant du dernier Tick, pour calculer les débits sur le temps réellement écoulé
+    last_tick: Instant,
+    // Arrivée du dernier relevé réussi (local ou distant)
+    last_update: Instant,
+    // Sources de la collecte locale, rafraîchies hors du fil de l'interface
+    sources: Arc<Mutex<LocalSources>>,
+    // Relevé local en cours : un Tick n'en lance pas d'autre tant qu'il n'est pas revenu
+    gathering: bool,
+    // Le prochain relevé local reprend les compteurs de maintenant, sans être affiché
+    rebase_pending: bool,
+    // Message temporaire affiché en haut de la fenêtre, avec son instant d'apparition
+    toast: Option<(String, Instant)>,
+    // Mesures et graphiques figés (barre d'espace)
+    paused: bool,
+    // Mode clair/sombre du système, pour la couleur d'accent
+    system_theme: theme::Mode,
+    // Hôte surveillé via SSH (`--remote user@host`) à la place de la machine locale
+    remote: Option<RemoteHost>,
+    // Comparaison avant/après : instantané marqué, puis écart avec l'instant présent
+    marked_snapshot: Option<(Snapshot, Instant)>,
+    comparison: Option<(SnapshotDelta, Duration)>,
+    // Références apprises à la demande pour l'alerte adaptative
+    #[cfg(not(feature = "minimal"))]
+    cpu_baseline: BaselineComparator,
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    down_baseline: BaselineComparator,
+    // Contrôle des débits affiché (`--debug`)
     #[cfg(feature = "network")]
-    networks: Networks,
+    debug: bool,
+    // Dernière erreur de chaque source de données, effacée dès qu'elle répond de nouveau
+    errors: HashMap<&'static str, String>,
+    // Journal CSV des mesures (`--log <fichier>`), abandonné à la première erreur d'écriture
+    metric_log: Option<BufWriter<File>>,
+    // Publication de l'instantané sur le bus de session, si le nom a pu être réservé
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    dbus: Option<DbusPublisher>,
+    // Export Prometheus (`--metrics-port <port>`, `--metrics-bind <adresse>`), si le port
+    // a pu être ouvert
+    #[cfg(feature = "metrics")]
+    metrics_server: Option<MetricsServer>,
+    // Ouvert à la première copie d'image, puis conservé (voir `ImageClipboard`)
+    #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+    image_clipboard: Option<ImageClipboard>,
+    #[cfg(feature = "network")]
+    rate_tracker: RateTracker,
     #[cfg(feature = "network")]
     down_mbps: f32,
     #[cfg(feature = "network")]
     up_mbps: f32,
+    // Débits instantanés les plus élevés depuis le lancement (ou la remise à zéro)
+    #[cfg(feature = "network")]
+    peak_down_mbps: f32,
+    #[cfg(feature = "network")]
+    peak_up_mbps: f32,
+    // Octets échangés depuis le démarrage de la machine
     #[cfg(feature = "network")]
-    down_history: Vec<f32>,
+    network_total_bytes: Option<(u64, u64)>,
+    // Contrôle de débogage : compteurs cumulés au début de la session, et octets
+    // retrouvés en intégrant les débits calculés à chaque Tick depuis
     #[cfg(feature = "network")]
-    up_history: Vec<f32>,
+    session_start_bytes: (u64, u64),
+    #[cfg(feature = "network")]
+    integrated_bytes: (f64, f64),
+    #[cfg(feature = "network")]
+    primary_interface: Option<String>,
+    #[cfg(feature = "network")]
+    primary_down_mbps: f32,
+    #[cfg(feature = "network")]
+    primary_up_mbps: f32,
+    // Interface ayant le plus contribué au dernier échantillon, avec sa part du trafic
+    #[cfg(feature = "network")]
+    top_interface: Option<(String, f32)>,
+    // Débits (Mbps) des interfaces les plus actives, puis cumul des autres
+    #[cfg(feature = "network")]
+    interface_rates: Vec<(String, f32, f32)>,
+    #[cfg(feature = "network")]
+    other_interfaces_rate: Option<(f32, f32)>,
+    #[cfg(feature = "netstat")]
+    ip_version_bytes: Option<(u64, u64)>,
     #[cfg(feature = "battery")]
     battery_percent: f32,
     #[cfg(feature = "battery")]
     battery_charging: bool,
-    #[cfg(feature = "battery")]
-    battery_history: Vec<f32>,
     #[cfg(feature = "disk")]
     disk_percent: f32,
     #[cfg(feature = "disk")]
-    disk_used_gb: u64,
+    disk_used_gb: f32,
     #[cfg(feature = "disk")]
-    disk_total_gb: u64,
+    disk_total_gb: f32,
+    // Lecture et écriture (Mo/s) sur l'ensemble des volumes, et leur somme dans le temps
     #[cfg(feature = "disk")]
-    disk_history: Vec<f32>,
+    disk_read_mb_s: f32,
     #[cfg(feature = "disk")]
-    disks: Disks,
+    disk_write_mb_s: f32,
+    #[cfg(feature = "processes")]
+    process_count: usize,
+    // Processus écrivant le plus sur disque : nom, pid et débit (Mo/s)
+    #[cfg(feature = "processes")]
+    disk_writers: Vec<(String, u32, f32)>,
+    // Dernier rafraîchissement de la liste des processus, base des débits d'écriture
+    #[cfg(feature = "processes")]
+    last_process_refresh: Instant,
+    // Pids du dernier relevé (aucun avant le premier), et processus (lancés, terminés) depuis
+    #[cfg(feature = "processes")]
+    previous_pids: Option<HashSet<u32>>,
+    #[cfg(feature = "processes")]
+    process_churn: (usize, usize),
+    #[cfg(feature = "processes")]
+    thread_count: usize,
+    #[cfg(feature = "temperature")]
+    core_temperatures: Vec<(String, f32)>,
+}
+
+// Sources sysinfo de la collecte locale. Leur rafraîchissement se fait dans un fil
+// à part (`refresh_local`) : une lecture bloquée ne gèle pas la fenêtre.
+struct LocalSources {
     sys: System,
+    #[cfg(feature = "network")]
+    networks: Networks,
+    #[cfg(feature = "disk")]
+    disks: Disks,
+    #[cfg(feature = "temperature")]
+    components: Components,
+}
+
+// Résultat d'un rafraîchissement des sources, avec les lectures faites hors de sysinfo
+#[derive(Debug, Clone)]
+struct LocalReadings {
+    refreshed_at: Instant,
+    // Rafraîchissement servant seulement à reprendre les compteurs (`rebase_pending`)
+    rebase: bool,
+    #[cfg(feature = "processes")]
+    processes_refreshed: bool,
+    #[cfg(feature = "battery")]
+    battery: Option<Result<(f32, bool), String>>,
+    #[cfg(feature = "netstat")]
+    ip_version_bytes: Option<Result<(u64, u64), String>>,
+    #[cfg(feature = "gpu")]
+    gpu_percent: Option<f32>,
 }
 
 pub fn main() -> iced::Result {
-    iced::application(new, update, view)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Mode diagnostic : écrit un rapport texte et quitte sans ouvrir de fenêtre
+    let diagnose_redacted = args.iter().any(|arg| arg == "--diagnose-redacted");
+    if diagnose_redacted || args.iter().any(|arg| arg == "--diagnose") {
+        write_diagnostics(diagnose_redacted);
+        return Ok(());
+    }
+
+    // Instantané JSON sur la sortie standard, lu notamment par `--remote`
+    if args.iter().any(|arg| arg == "--json" || arg == "--snapshot") {
+        print_json_snapshot();
+        return Ok(());
+    }
+
+    // Mode profilage : mesure le coût de la collecte sans ouvrir de fenêtre
+    if let Some(index) = args.iter().position(|arg| arg == "--profile") {
+        let seconds = args
+            .get(index + 1)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(PROFILE_DEFAULT_SECS);
+        run_profile(Duration::from_secs(seconds));
+        return Ok(());
+    }
+
+    let remote = args
+        .iter()
+        .position(|arg| arg == "--remote")
+        .and_then(|index| args.get(index + 1))
+        .cloned();
+    let debug = args.iter().any(|arg| arg == "--debug");
+    let log_path = args
+        .iter()
+        .position(|arg| arg == "--log")
+        .and_then(|index| args.get(index + 1))
+        .map(PathBuf::from);
+    let metrics_port = args
+        .iter()
+        .position(|arg| arg == "--metrics-port")
+        .and_then(|index| args.get(index + 1))
+        .and_then(|port| port.parse::<u16>().ok());
+    if metrics_port.is_some() && !cfg!(feature = "metrics") {
+        eprintln!("--metrics-port ignoré : compiler avec la feature `metrics`");
+    }
+    // Boucle locale par défaut : l'export n'a pas d'authentification
+    let metrics_bind = match args.iter().position(|arg| arg == "--metrics-bind") {
+        Some(index) => match args.get(index + 1).map(|addr| addr.parse::<IpAddr>()) {
+            Some(Ok(addr)) => addr,
+            _ => {
+                eprintln!("--metrics-bind invalide : écoute sur {}", Ipv4Addr::LOCALHOST);
+                Ipv4Addr::LOCALHOST.into()
+            }
+        },
+        None => Ipv4Addr::LOCALHOST.into(),
+    };
+    let metrics_addr = metrics_port.map(|port| SocketAddr::new(metrics_bind, port));
+
+    iced::application(
+        move || new(remote.clone(), debug, log_path.as_deref(), metrics_addr),
+        update,
+        view,
+    )
         .subscription(subscription)
+        .exit_on_close_request(false)
         .window(iced::window::Settings {
             size: iced::Size::new(1400.0, 900.0),
             ..Default::default()
@@ -76,8 +345,193 @@ pub fn main() -> iced::Result {
         .run()
 }
 
-fn new() -> State {
-    let mut sys = System::new_all();
+fn print_json_snapshot() {
+    let config = Config::load();
+    let mut collector = SnapshotCollector::new(&config.enabled);
+
+    // Deux relevés espacés sont nécessaires pour l'utilisation CPU et les débits
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+    match collector.gather_snapshot(&config).to_json() {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("Impossible de sérialiser l'instantané : {}", err),
+    }
+}
+
+const PROFILE_DEFAULT_SECS: u64 = 10;
+
+fn run_profile(duration: Duration) {
+    let config = Config::load();
+    let mut collector = SnapshotCollector::new(&config.enabled);
+    let mut totals = GatherTimings::default();
+    let mut samples = 0_u32;
+
+    println!(
+        "Profilage de la collecte pendant {} s (intervalle {} ms)…",
+        duration.as_secs(),
+        config.effective_refresh_ms()
+    );
+
+    let started = Instant::now();
+    while started.elapsed() < duration {
+        std::thread::sleep(Duration::from_millis(config.effective_refresh_ms()));
+        collector.gather_snapshot(&config);
+        totals.add(collector.last_timings());
+        samples += 1;
+    }
+
+    if samples == 0 {
+        return;
+    }
+
+    println!("\n{:<14} {:>12}", "Sous-système", "Moyenne");
+    println!("{:-<14} {:->12}", "", "");
+    for (name, total) in totals.entries() {
+        println!("{:<14} {:>9.3} ms", name, total.as_secs_f64() * 1000.0 / samples as f64);
+    }
+    println!("{:-<14} {:->12}", "", "");
+    println!(
+        "{:<14} {:>9.3} ms  ({} relevés)",
+        "total",
+        totals.total().as_secs_f64() * 1000.0 / samples as f64,
+        samples
+    );
+}
+
+fn fetch_remote(target: String) -> Task<Message> {
+    let (sender, receiver) = iced::futures::channel::oneshot::channel();
+
+    // ssh est bloquant : il s'exécute hors de la boucle de l'interface
+    std::thread::spawn(move || {
+        let _ = sender.send(fetch_remote_snapshot(&target));
+    });
+
+    Task::perform(
+        async move {
+            receiver
+                .await
+                .unwrap_or_else(|_| Err("connexion interrompue".to_string()))
+        },
+        Message::RemoteSnapshot,
+    )
+}
+
+// Rafraîchit les sources locales hors de la boucle de l'interface, puis lit la
+// batterie et les compteurs IPv4/IPv6 (commande et fichiers qui peuvent aussi bloquer)
+fn refresh_local(
+    sources: Arc<Mutex<LocalSources>>,
+    enabled: EnabledMetrics,
+    processes: bool,
+    rebase: bool,
+) -> Task<Message> {
+    let (sender, receiver) = iced::futures::channel::oneshot::channel();
+
+    std::thread::spawn(move || {
+        let mut guard = sources.lock().unwrap_or_else(PoisonError::into_inner);
+        let sources = &mut *guard;
+        sources.sys.refresh_cpu_usage();
+        sources.sys.refresh_memory();
+        #[cfg(feature = "network")]
+        if enabled.network {
+            sources.networks.refresh(true);
+        }
+        #[cfg(feature = "disk")]
+        if enabled.disk {
+            sources.disks.refresh(true);
+        }
+        #[cfg(feature = "temperature")]
+        sources.components.refresh(false);
+        #[cfg(feature = "processes")]
+        if processes {
+            refresh_processes(&mut sources.sys);
+        }
+        #[cfg(not(feature = "processes"))]
+        let _ = processes;
+        #[cfg(not(any(feature = "network", feature = "disk", feature = "battery")))]
+        let _ = enabled;
+        let refreshed_at = Instant::now();
+        drop(guard);
+
+        let readings = LocalReadings {
+            refreshed_at,
+            rebase,
+            #[cfg(feature = "processes")]
+            processes_refreshed: processes,
+            #[cfg(feature = "battery")]
+            battery: (enabled.battery && !rebase).then(read_battery_info),
+            #[cfg(feature = "netstat")]
+            ip_version_bytes: (enabled.network && !rebase).then(|| {
+                ip_version_bytes().ok_or_else(|| "compteurs IPv4/IPv6 illisibles".to_string())
+            }),
+            #[cfg(feature = "gpu")]
+            gpu_percent: if rebase { None } else { gpu_usage().map(|(utilization, _)| utilization) },
+        };
+        let _ = sender.send(readings);
+    });
+
+    Task::perform(
+        async move {
+            receiver
+                .await
+                .map_err(|_| "relevé local interrompu".to_string())
+        },
+        Message::LocalRefreshed,
+    )
+}
+
+const METRIC_LOG_HEADER: &str = "timestamp,cpu,ram_percent,down_mbps,up_mbps";
+
+// Le journal est complété d'un lancement à l'autre : l'en-tête n'est écrit que
+// dans un fichier vide
+fn open_metric_log(path: &Path) -> std::io::Result<BufWriter<File>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    if writer.get_ref().metadata()?.len() == 0 {
+        writeln!(writer, "{}", METRIC_LOG_HEADER)?;
+    }
+    Ok(writer)
+}
+
+#[cfg(not(feature = "minimal"))]
+fn history_export_path() -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    PathBuf::from(format!("monitor_app-history-{}.csv", timestamp))
+}
+
+fn write_diagnostics(redacted: bool) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = format!("monitor_app-diagnostics-{}.txt", timestamp);
+
+    match std::fs::write(&path, diagnostics_report(redacted)) {
+        Ok(()) => println!("Rapport de diagnostic écrit dans {}", path),
+        Err(err) => eprintln!("Impossible d'écrire {} : {}", path, err),
+    }
+}
+
+fn new(
+    remote: Option<String>,
+    debug: bool,
+    log_path: Option<&Path>,
+    metrics_addr: Option<SocketAddr>,
+) -> (State, Task<Message>) {
+    let config = Config::load();
+    if config.effective_refresh_ms() != config.refresh_ms {
+        eprintln!(
+            "Intervalle de {} ms trop court pour mesurer le CPU : {} ms utilisés",
+            config.refresh_ms,
+            config.effective_refresh_ms()
+        );
+    }
+    #[cfg(not(feature = "minimal"))]
+    let charts = Charts::new(&config);
+
+    let mut sys = System::new_with_specifics(system_refresh_kind(&config.enabled));
     sys.refresh_cpu_usage();
     sys.refresh_memory();
 
@@ -85,39 +539,123 @@ fn new() -> State {
     let mut networks = Networks::new_with_refreshed_list();
     #[cfg(feature = "network")]
     networks.refresh(true);
+    #[cfg(feature = "network")]
+    let mut rate_tracker = RateTracker::new();
+    #[cfg(feature = "network")]
+    rate_tracker.update_for(&networks, config.interfaces.as_deref());
+    #[cfg(feature = "network")]
+    let session_start_bytes = network_total_bytes(&networks, config.interfaces.as_deref());
+    // Seul le module réseau a des informations de débogage
+    #[cfg(not(feature = "network"))]
+    let _ = debug;
 
     #[cfg(feature = "disk")]
     let disks = Disks::new_with_refreshed_list();
 
+    #[cfg(feature = "temperature")]
+    let components = Components::new_with_refreshed_list();
+
     #[cfg(feature = "battery")]
-    let (battery_percent, battery_charging) = get_battery_info();
+    let (battery_percent, battery_charging) = if config.enabled.battery {
+        get_battery_info()
+    } else {
+        (100.0, false)
+    };
     
     #[cfg(feature = "disk")]
-    let (disk_percent, disk_used_gb, disk_total_gb) = get_disk_usage(&disks);
+    let DiskUsage {
+        percent: disk_percent,
+        used_gb: disk_used_gb,
+        total_gb: disk_total_gb,
+    } = scoped_disk_usage(&disks, config.disk_scope);
+
+    // La liste des processus est chargée par `System::new_with_specifics`
+    #[cfg(feature = "processes")]
+    let (process_count, thread_count) = process_counts(&sys);
+
+    let cpu = cpu_usage(&sys, config.cpu_source);
+    let (used_mem_mb, available_mem_mb, total_mem_mb) = memory_usage(&sys);
+
+    // Un fichier impossible à ouvrir n'empêche pas le lancement : le journal est désactivé
+    let metric_log = log_path.and_then(|path| match open_metric_log(path) {
+        Ok(writer) => Some(writer),
+        Err(err) => {
+            eprintln!("Journal {} désactivé : {}", path.display(), err);
+            None
+        }
+    });
 
     let mut state = State {
-        cpu: sys.global_cpu_usage(),
-        used_mem_mb: sys.used_memory() / 1024,
-        total_mem_mb: sys.total_memory() / 1024,
+        config,
+        cpu,
+        core_usages: per_core_usage(&sys),
+        load_average: load_average(),
+        used_mem_mb,
+        available_mem_mb,
+        total_mem_mb,
+        swap_percent: swap_percent(&sys),
+        #[cfg(feature = "gpu")]
+        gpu_percent: None,
         current_tab: Tab::System,
-        cpu_history: Vec::new(),
-        ram_history: Vec::new(),
+        #[cfg(not(feature = "minimal"))]
+        charts,
+        ticks: 0,
+        last_tick: Instant::now(),
+        last_update: Instant::now(),
+        toast: None,
+        paused: false,
+        system_theme: theme::Mode::None,
+        remote: remote.map(RemoteHost::new),
+        marked_snapshot: None,
+        comparison: None,
+        #[cfg(not(feature = "minimal"))]
+        cpu_baseline: BaselineComparator::default(),
+        #[cfg(all(feature = "network", not(feature = "minimal")))]
+        down_baseline: BaselineComparator::default(),
+        #[cfg(feature = "network")]
+        debug,
+        errors: HashMap::new(),
+        metric_log,
+        #[cfg(all(feature = "dbus", target_os = "linux"))]
+        dbus: None,
+        #[cfg(feature = "metrics")]
+        metrics_server: None,
+        #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+        image_clipboard: None,
         #[cfg(feature = "network")]
-        networks,
+        rate_tracker,
         #[cfg(feature = "network")]
         down_mbps: 0.0,
         #[cfg(feature = "network")]
         up_mbps: 0.0,
         #[cfg(feature = "network")]
-        down_history: Vec::new(),
+        peak_down_mbps: 0.0,
+        #[cfg(feature = "network")]
+        peak_up_mbps: 0.0,
+        #[cfg(feature = "network")]
+        network_total_bytes: Some(session_start_bytes),
+        #[cfg(feature = "network")]
+        session_start_bytes,
+        #[cfg(feature = "network")]
+        integrated_bytes: (0.0, 0.0),
+        #[cfg(feature = "network")]
+        primary_interface: None,
+        #[cfg(feature = "network")]
+        primary_down_mbps: 0.0,
         #[cfg(feature = "network")]
-        up_history: Vec::new(),
+        primary_up_mbps: 0.0,
+        #[cfg(feature = "network")]
+        top_interface: None,
+        #[cfg(feature = "network")]
+        interface_rates: Vec::new(),
+        #[cfg(feature = "network")]
+        other_interfaces_rate: None,
+        #[cfg(feature = "netstat")]
+        ip_version_bytes: ip_version_bytes(),
         #[cfg(feature = "battery")]
         battery_percent,
         #[cfg(feature = "battery")]
         battery_charging,
-        #[cfg(feature = "battery")]
-        battery_history: Vec::new(),
         #[cfg(feature = "disk")]
         disk_percent,
         #[cfg(feature = "disk")]
@@ -125,197 +663,792 @@ fn new() -> State {
         #[cfg(feature = "disk")]
         disk_total_gb,
         #[cfg(feature = "disk")]
-        disk_history: Vec::new(),
+        disk_read_mb_s: 0.0,
         #[cfg(feature = "disk")]
-        disks,
-        sys,
+        disk_write_mb_s: 0.0,
+        #[cfg(feature = "processes")]
+        process_count,
+        #[cfg(feature = "processes")]
+        disk_writers: Vec::new(),
+        #[cfg(feature = "processes")]
+        last_process_refresh: Instant::now(),
+        #[cfg(feature = "processes")]
+        previous_pids: None,
+        #[cfg(feature = "processes")]
+        process_churn: (0, 0),
+        #[cfg(feature = "processes")]
+        thread_count,
+        #[cfg(feature = "temperature")]
+        core_temperatures: per_core_temperatures(&components),
+        sources: Arc::new(Mutex::new(LocalSources {
+            sys,
+            #[cfg(feature = "network")]
+            networks,
+            #[cfg(feature = "disk")]
+            disks,
+            #[cfg(feature = "temperature")]
+            components,
+        })),
+        gathering: false,
+        rebase_pending: false,
     };
 
+    #[cfg(all(feature = "dbus", target_os = "linux"))]
+    {
+        state.dbus = state.track_error("dbus", DbusPublisher::start());
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Some(addr) = metrics_addr {
+        state.metrics_server = state.track_error("metrics", MetricsServer::start(addr));
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = metrics_addr;
+
+    #[cfg(not(feature = "minimal"))]
+    if state.config.persist_history {
+        state.restore_history();
+    }
+
     state.push_samples();
-    state
+
+    // Les changements ultérieurs arrivent par `system::theme_changes`
+    (state, system::theme().map(Message::SystemThemeChanged))
 }
 
 fn update(state: &mut State, message: Message) -> Task<Message> {
     match message {
         Message::Tick => {
-            state.sys.refresh_cpu_usage();
-            state.sys.refresh_memory();
-            
-            #[cfg(feature = "network")]
-            state.networks.refresh(true);
-            
+            let now = Instant::now();
+
+            if state
+                .toast
+                .as_ref()
+                .is_some_and(|(_, shown_at)| now.duration_since(*shown_at) >= State::TOAST_DURATION)
+            {
+                state.toast = None;
+            }
+
+            if state.paused {
+                return Task::none();
+            }
+
+            // Mode distant : les mesures arrivent par `Message::RemoteSnapshot`
+            if let Some(remote) = &mut state.remote {
+                if remote.should_fetch(now) {
+                    remote.pending = true;
+                    return fetch_remote(remote.target.clone());
+                }
+                return Task::none();
+            }
+
+            // Relevé précédent toujours en cours (lecture bloquée) : on n'en empile pas
+            // d'autre, et l'âge des valeurs affichées continue d'augmenter
+            if state.gathering {
+                return Task::none();
+            }
+            state.gathering = true;
+
+            let rebase = std::mem::take(&mut state.rebase_pending);
+            #[cfg(feature = "processes")]
+            let processes = state.config.enabled.processes
+                && (rebase || (state.ticks + 1).is_multiple_of(State::PROCESS_REFRESH_TICKS));
+            #[cfg(not(feature = "processes"))]
+            let processes = false;
+
+            return refresh_local(Arc::clone(&state.sources), state.config.enabled, processes, rebase);
+        }
+        Message::LocalRefreshed(result) => {
+            state.gathering = false;
+            let Some(readings) = state.track_error("collecte", result) else {
+                return Task::none();
+            };
+            // Relevé lancé avant une pause ou une réactivation de module : il couvre
+            // l'interruption, le suivant reprendra les compteurs
+            if state.paused || state.rebase_pending {
+                return Task::none();
+            }
+
+            // Libre : le fil de collecte l'a rendu avant d'envoyer ses lectures
+            let sources = Arc::clone(&state.sources);
+            let sources = sources.lock().unwrap_or_else(PoisonError::into_inner);
+            if readings.rebase {
+                state.rebase_counters(&sources, readings.refreshed_at);
+                return Task::none();
+            }
+
+            state.ticks += 1;
+
+            let now = readings.refreshed_at;
+            #[cfg(any(feature = "network", feature = "disk"))]
+            let elapsed_secs = now.duration_since(state.last_tick).as_secs_f32().max(0.001);
+            state.last_tick = now;
+
             #[cfg(feature = "disk")]
-            state.disks.refresh(true);
+            if state.config.enabled.disk {
+                let (read, written) = disk_io_deltas(&sources.disks);
+                state.disk_read_mb_s = read as f32 / 1_048_576.0 / elapsed_secs;
+                state.disk_write_mb_s = written as f32 / 1_048_576.0 / elapsed_secs;
+            }
 
-            state.cpu = state.sys.global_cpu_usage();
-            state.used_mem_mb = state.sys.used_memory() / 1024;
-            state.total_mem_mb = state.sys.total_memory() / 1024;
+            state.cpu = cpu_usage(&sources.sys, state.config.cpu_source);
+            state.core_usages = per_core_usage(&sources.sys);
+            state.load_average = load_average();
 
-            #[cfg(feature = "network")]
+            #[cfg(feature = "temperature")]
+            {
+                state.core_temperatures = per_core_temperatures(&sources.components);
+            }
+            (state.used_mem_mb, state.available_mem_mb, state.total_mem_mb) =
+                memory_usage(&sources.sys);
+            state.swap_percent = swap_percent(&sources.sys);
+            #[cfg(feature = "gpu")]
             {
-                let (delta_rx, delta_tx) = network_deltas(&state.networks);
-                state.down_mbps = delta_rx as f32 * 8.0 / 1_000_000.0;
-                state.up_mbps = delta_tx as f32 * 8.0 / 1_000_000.0;
+                state.gpu_percent = readings.gpu_percent;
+            }
+
+            #[cfg(feature = "network")]
+            if state.config.enabled.network {
+                let allowlist = state.config.interfaces.as_deref();
+                let (delta_rx, delta_tx) = state.rate_tracker.update_for(&sources.networks, allowlist);
+                state.down_mbps = delta_rx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+                state.up_mbps = delta_tx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+
+                let to_bytes = |mbps: f32| f64::from(mbps) * f64::from(elapsed_secs) * 1_000_000.0 / 8.0;
+                state.integrated_bytes.0 += to_bytes(state.down_mbps);
+                state.integrated_bytes.1 += to_bytes(state.up_mbps);
+
+                // Le graphique suit l'interface la plus active, réévaluée périodiquement,
+                // sauf si l'utilisateur a choisi lui-même les interfaces à suivre
+                if allowlist.is_some() {
+                    state.primary_interface = None;
+                } else if state.ticks.is_multiple_of(State::INTERFACE_EVAL_TICKS) {
+                    state.primary_interface =
+                        dominant_interface(&sources.networks, &state.primary_interface);
+                }
+
+                let (primary_rx, primary_tx) = state
+                    .primary_interface
+                    .as_deref()
+                    .and_then(|name| interface_deltas(&sources.networks, name))
+                    .unwrap_or((delta_rx, delta_tx));
+                state.primary_down_mbps = primary_rx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+                state.primary_up_mbps = primary_tx as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+
+                let mut deltas = per_interface_deltas(&sources.networks);
+                deltas.retain(|(name, _, _)| is_allowed_interface(name, allowlist));
+                state.top_interface = top_contributor(&deltas);
+                state.network_total_bytes = Some(network_total_bytes(&sources.networks, allowlist));
+                state.record_network_peaks();
+
+                let to_mbps = |bytes: u64| bytes as f32 * 8.0 / 1_000_000.0 / elapsed_secs;
+                let (shown, others) = top_interfaces(&deltas, state.config.max_interfaces);
+                state.interface_rates = shown
+                    .iter()
+                    .map(|(name, rx, tx)| (name.clone(), to_mbps(*rx), to_mbps(*tx)))
+                    .collect();
+                state.other_interfaces_rate = others.map(|(rx, tx)| (to_mbps(rx), to_mbps(tx)));
+            }
+
+            #[cfg(feature = "processes")]
+            if readings.processes_refreshed {
+                (state.process_count, state.thread_count) = process_counts(&sources.sys);
+
+                let pids = process_pids(&sources.sys);
+                state.process_churn = state
+                    .previous_pids
+                    .as_ref()
+                    .map_or((0, 0), |previous| process_churn(previous, &pids));
+                state.previous_pids = Some(pids);
+
+                let elapsed_secs = now.duration_since(state.last_process_refresh).as_secs_f32().max(0.001);
+                state.last_process_refresh = now;
+                let to_mb_per_sec = |bytes: u64| bytes as f32 / 1_048_576.0 / elapsed_secs;
+                state.disk_writers = top_processes_by_disk_write(&sources.sys, State::TOP_DISK_WRITERS)
+                    .into_iter()
+                    .map(|(name, pid, written)| (name, pid, to_mb_per_sec(written)))
+                    .collect();
             }
 
+            #[cfg(feature = "netstat")]
+            if let Some(counters) = readings.ip_version_bytes {
+                state.ip_version_bytes = state.track_error("netstat", counters);
+            }
+
+            // En cas d'échec, la dernière lecture reste affichée
             #[cfg(feature = "battery")]
-            {
-                let (battery_percent, battery_charging) = get_battery_info();
-                state.battery_percent = battery_percent;
-                state.battery_charging = battery_charging;
+            if let Some(battery) = readings.battery {
+                if let Some((battery_percent, battery_charging)) = state.track_error("batterie", battery) {
+                    state.battery_percent = battery_percent;
+                    state.battery_charging = battery_charging;
+                }
             }
 
             #[cfg(feature = "disk")]
-            {
-                let (disk_percent, disk_used_gb, disk_total_gb) = get_disk_usage(&state.disks);
-                state.disk_percent = disk_percent;
-                state.disk_used_gb = disk_used_gb;
-                state.disk_total_gb = disk_total_gb;
+            if state.config.enabled.disk {
+                state.refresh_disk_usage(&sources.disks);
             }
 
             state.push_samples();
+            state.log_metrics();
+
+            state.last_update = Instant::now();
+            state.publish_snapshot();
         }
         Message::TabSelected(tab) => {
             state.current_tab = tab;
         }
+        Message::SetInterval(refresh_ms) => {
+            state.config.refresh_ms = refresh_ms.clamp(State::MIN_INTERVAL_MS, State::MAX_INTERVAL_MS);
+            state.toast = Some((
+                format!("Intervalle : {}", format_interval(state.config.refresh_ms)),
+                Instant::now(),
+            ));
+            state.save_config();
+        }
+        #[cfg(not(feature = "minimal"))]
+        Message::SetHistoryLen(history_len) => {
+            state.config.history_len = history_len;
+            state.charts.resize(history_len);
+            state.save_config();
+        }
+        #[cfg(not(feature = "minimal"))]
+        Message::ToggleReduceMotion => {
+            state.config.reduce_motion = !state.config.reduce_motion;
+            state.save_config();
+        }
+        #[cfg(not(feature = "minimal"))]
+        Message::ToggleChartBaseline => {
+            state.config.chart_baseline = match state.config.chart_baseline {
+                BaselineMode::Zero => BaselineMode::Min,
+                BaselineMode::Min => BaselineMode::Zero,
+            };
+            state.charts.caches.clear();
+            state.save_config();
+        }
+        Message::ToggleModule(module) => {
+            state.config.enabled.toggle(module);
+            if state.config.enabled.is_enabled(module) {
+                state.rebase_pending = true;
+            }
+            state.save_config();
+        }
+        Message::SetIconSet(icons) => {
+            state.config.icons = icons;
+            state.save_config();
+        }
+        #[cfg(feature = "disk")]
+        Message::ToggleDiskScope => {
+            state.config.disk_scope = state.config.disk_scope.toggled();
+            // Pendant un relevé, c'est lui qui appliquera la nouvelle portée
+            let sources = Arc::clone(&state.sources);
+            if let Ok(sources) = sources.try_lock() {
+                state.refresh_disk_usage(&sources.disks);
+            }
+            state.save_config();
+        }
+        Message::RemoteSnapshot(result) => {
+            let Some(remote) = &mut state.remote else {
+                return Task::none();
+            };
+
+            let result = result.map_err(|err| format!("{} : {}", remote.target, err));
+            match &result {
+                Ok(_) => remote.on_success(),
+                Err(_) => remote.on_failure(Instant::now()),
+            }
+            if let Some(snapshot) = state.track_error("distant", result) {
+                state.apply_snapshot(&snapshot);
+                state.last_update = Instant::now();
+                state.publish_snapshot();
+            }
+        }
+        #[cfg(feature = "network")]
+        Message::ResetNetworkPeak => {
+            state.peak_down_mbps = 0.0;
+            state.peak_up_mbps = 0.0;
+        }
+        Message::ResetAll => {
+            state.reset_all();
+            state.toast = Some(("Statistiques réinitialisées".to_string(), Instant::now()));
+        }
+        #[cfg(not(feature = "minimal"))]
+        Message::LearnBaseline => {
+            state.cpu_baseline.learn(&state.charts.cpu);
+            #[cfg(feature = "network")]
+            state.down_baseline.learn(&state.charts.down);
+            state.toast = Some((
+                format!("Référence apprise sur {} points", state.charts.cpu.len()),
+                Instant::now(),
+            ));
+        }
+        Message::MarkSnapshot => {
+            state.marked_snapshot = Some((state.snapshot(), Instant::now()));
+            state.comparison = None;
+        }
+        Message::CompareSnapshot => {
+            if let Some((marked, marked_at)) = &state.marked_snapshot {
+                state.comparison = Some((snapshot_delta(marked, &state.snapshot()), marked_at.elapsed()));
+            }
+        }
+        Message::SystemThemeChanged(mode) => {
+            state.system_theme = mode;
+        }
+        Message::CloseRequested => {
+            if let Some(Err(err)) = state.metric_log.as_mut().map(Write::flush) {
+                eprintln!("Impossible d'écrire le journal des mesures : {}", err);
+            }
+            #[cfg(not(feature = "minimal"))]
+            if state.config.persist_history {
+                let result = state.saved_history().save().map_err(|err| err.to_string());
+                state.track_error("historique", result);
+            }
+            return iced::exit();
+        }
+        #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+        Message::CopyChartImage => {
+            let message = match state.copy_chart_image() {
+                Ok(()) => "Graphique copié dans le presse-papiers".to_string(),
+                Err(err) => format!("Copie impossible : {}", err),
+            };
+            state.toast = Some((message, Instant::now()));
+        }
+        #[cfg(not(feature = "minimal"))]
+        Message::ExportHistory(path) => {
+            let message = match std::fs::write(&path, state.history_csv()) {
+                Ok(()) => format!("Historique exporté dans {}", path.display()),
+                Err(err) => format!("Export impossible : {}", err),
+            };
+            state.toast = Some((message, Instant::now()));
+        }
+        Message::TogglePause => {
+            state.paused = !state.paused;
+
+            // Au redémarrage, les compteurs repartent du prochain relevé : le trafic
+            // écoulé pendant la pause ne doit pas apparaître comme un pic
+            if !state.paused {
+                state.rebase_pending = true;
+                state.last_update = Instant::now();
+            }
+        }
     }
 
     Task::none()
 }
 
-fn subscription(_state: &State) -> Subscription<Message> {
-    time::every(Duration::from_millis(1_000)).map(|_| Message::Tick)
+fn subscription(state: &State) -> Subscription<Message> {
+    Subscription::batch([
+        time::every(Duration::from_millis(state.config.effective_refresh_ms())).map(|_| Message::Tick),
+        system::theme_changes().map(Message::SystemThemeChanged),
+        window::close_requests().map(|_| Message::CloseRequested),
+        keyboard::listen()
+            .with(state.config.refresh_ms)
+            .filter_map(|(refresh_ms, event)| key_shortcut(refresh_ms, event)),
+    ])
 }
 
-fn view(state: &State) -> Element<'_, Message> {
-    let cpu_percent = state.cpu;
-    let ram_text = if state.total_mem_mb > 0 {
-        let used_gib = state.used_mem_mb as f32 / 1024.0;
-        let total_gib = state.total_mem_mb as f32 / 1024.0;
-        format!("{:.2} / {:.2} GiB", used_gib, total_gib)
-    } else {
-        "(en attente)".to_string()
+// Raccourcis clavier : `+` divise l'intervalle par deux, `-` le double,
+// espace met en pause, `r` réinitialise les statistiques, `c` copie le graphique
+// de l'onglet en image (feature `clipboard-image`)
+fn key_shortcut(refresh_ms: u64, event: keyboard::Event) -> Option<Message> {
+    let keyboard::Event::KeyPressed { modified_key, .. } = event else {
+        return None;
     };
 
-    let ram_percent = if state.total_mem_mb > 0 {
+    match modified_key.as_ref() {
+        keyboard::Key::Named(keyboard::key::Named::Space) => Some(Message::TogglePause),
+        keyboard::Key::Character("r") => Some(Message::ResetAll),
+        #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+        keyboard::Key::Character("c") => Some(Message::CopyChartImage),
+        keyboard::Key::Character("+") => Some(Message::SetInterval(
+            (refresh_ms / 2).max(State::MIN_INTERVAL_MS),
+        )),
+        keyboard::Key::Character("-") => Some(Message::SetInterval(
+            (refresh_ms * 2).min(State::MAX_INTERVAL_MS),
+        )),
+        _ => None,
+    }
+}
+
+fn view(state: &State) -> Element<'_, Message> {
+    let icons = state.config.icons;
+    let accent = state.accent();
+    // Valeurs lissées comme leur courbe (identiques aux mesures sans lissage)
+    let cpu_percent = state.displayed("cpu", state.cpu);
+    // Mémoire relevée en Mo (voir `memory_usage`), affichée en GiB
+    let used_gib = state.used_mem_mb as f32 / 1024.0;
+    let total_gib = state.total_mem_mb as f32 / 1024.0;
+    let ram_text = format!("{:.2} / {:.2} GiB", used_gib, total_gib);
+
+    let ram_percent = if memory_available(state.total_mem_mb) {
         (state.used_mem_mb as f32 / state.total_mem_mb as f32) * 100.0
     } else {
         0.0
     };
-
-    #[cfg(feature = "network")]
-    let (total_rx_gib, total_tx_gib) = network_totals(&state.networks);
-
-    #[cfg(feature = "network")]
-    let down_max = state.down_history.iter().copied().fold(1.0_f32, f32::max);
-    #[cfg(feature = "network")]
-    let up_max = state.up_history.iter().copied().fold(1.0_f32, f32::max);
-
-    let cpu_chart = Canvas::new(Sparkline {
-        data: &state.cpu_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: 100.0,
-    })
-    .height(Pixels(100.0))
-    .width(Length::Fill);
-
-    let ram_chart = Canvas::new(Sparkline {
-        data: &state.ram_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: 100.0,
-    })
-    .height(Pixels(100.0))
-    .width(Length::Fill);
-
+    let ram_percent = state.displayed("ram", ram_percent);
+    #[cfg(feature = "battery")]
+    let battery_percent = state.displayed("battery", state.battery_percent);
+    #[cfg(feature = "disk")]
+    let disk_percent = state.displayed("disk", state.disk_percent);
     #[cfg(feature = "network")]
-    let net_down_chart = Canvas::new(Sparkline {
-        data: &state.down_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: down_max,
-    })
-    .height(Pixels(80.0))
-    .width(Length::Fill);
+    let (down_mbps, up_mbps) = (
+        state.displayed("down_total_mbps", state.down_mbps),
+        state.displayed("up_total_mbps", state.up_mbps),
+    );
 
+    // Cumul depuis le démarrage, celui de la machine observée en mode distant
     #[cfg(feature = "network")]
-    let net_up_chart = Canvas::new(Sparkline {
-        data: &state.up_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: up_max,
-    })
-    .height(Pixels(80.0))
-    .width(Length::Fill);
+    let network_totals_gib = state
+        .network_total_bytes
+        .map(|(rx, tx)| (rx as f32 / 1_073_741_824.0, tx as f32 / 1_073_741_824.0));
+
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    let down_max = state.charts.down.iter().copied().fold(1.0_f32, f32::max);
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    let up_max = state.charts.up.iter().copied().fold(1.0_f32, f32::max);
+
+    #[cfg(not(feature = "minimal"))]
+    let history_label = || {
+        Some(
+            text(state.history_label())
+                .size(14)
+                .color(Color::from_rgba8(255, 255, 255, 0.8)),
+        )
+    };
+    // Profil `minimal` : ni graphiques ni durée d'historique, seulement les valeurs
+    #[cfg(feature = "minimal")]
+    let history_label = || None::<Element<'_, Message>>;
+
+    #[cfg(not(feature = "minimal"))]
+    let cpu_chart = Some(
+        Canvas::new(metric_sparkline(state, builtin_metric("cpu"), &state.charts.cpu, &state.charts.caches.cpu))
+            .height(Pixels(100.0))
+            .width(Length::Fill),
+    );
+    #[cfg(feature = "minimal")]
+    let cpu_chart: Option<Element<'_, Message>> = None;
+
+    #[cfg(not(feature = "minimal"))]
+    let ram_chart = Some(
+        Canvas::new(metric_sparkline(state, builtin_metric("ram"), &state.charts.ram, &state.charts.caches.ram))
+            .height(Pixels(100.0))
+            .width(Length::Fill),
+    );
+    #[cfg(feature = "minimal")]
+    let ram_chart: Option<Element<'_, Message>> = None;
+
+    // Valeur actuelle et haut du graphique, dans l'unité choisie pour ce haut
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    let chart_scale = |label: &str, current: f32, max: f32| {
+        let unit = RateUnit::for_value(max);
+        text(format!("{} {} · max {}", label, unit.format(current), unit.format(max)))
+            .size(12)
+            .color(Color::from_rgba8(255, 255, 255, 0.7))
+    };
 
-    #[cfg(feature = "battery")]
-    let battery_chart = Canvas::new(Sparkline {
-        data: &state.battery_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: 100.0,
-    })
-    .height(Pixels(80.0))
-    .width(Length::Fill);
+    // Débits de l'interface principale, chacun sous son échelle
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    let net_charts = Some(
+        column![
+            text(match &state.primary_interface {
+                Some(name) => format!("{} · {}", state.history_label(), name),
+                None => format!("{} · toutes interfaces", state.history_label()),
+            })
+            .size(14)
+            .color(Color::from_rgba8(255, 255, 255, 0.8)),
+            chart_scale("↓", state.displayed("down_mbps", state.primary_down_mbps), down_max),
+            Canvas::new(
+                metric_sparkline(state, builtin_metric("down_mbps"), &state.charts.down, &state.charts.caches.down)
+                    .guides(),
+            )
+            .height(Pixels(80.0))
+            .width(Length::Fill),
+            chart_scale("↑", state.displayed("up_mbps", state.primary_up_mbps), up_max),
+            Canvas::new(
+                metric_sparkline(state, builtin_metric("up_mbps"), &state.charts.up, &state.charts.caches.up)
+                    .guides(),
+            )
+            .height(Pixels(80.0))
+            .width(Length::Fill),
+        ]
+        .spacing(10),
+    );
+    #[cfg(all(feature = "network", feature = "minimal"))]
+    let net_charts: Option<Element<'_, Message>> = None;
+
+    #[cfg(all(feature = "battery", not(feature = "minimal")))]
+    let battery_chart = Some(
+        Canvas::new(metric_sparkline(
+            state,
+            builtin_metric("battery"),
+            &state.charts.battery,
+            &state.charts.caches.battery,
+        ))
+        .height(Pixels(80.0))
+        .width(Length::Fill),
+    );
+    #[cfg(all(feature = "battery", feature = "minimal"))]
+    let battery_chart: Option<Element<'_, Message>> = None;
 
+    // Une batterie vide est critique : on mesure la charge manquante
     #[cfg(feature = "battery")]
-    let battery_color = if state.battery_percent > 50.0 {
-        Color::from_rgb8(0x10, 0xb9, 0x81)
-    } else if state.battery_percent > 20.0 {
-        Color::from_rgb8(0xf5, 0x9e, 0x0b)
-    } else {
-        Color::from_rgb8(0xef, 0x44, 0x44)
-    };
+    let battery_color = threshold_color(100.0 - state.battery_percent, 50.0, 80.0);
 
     #[cfg(feature = "battery")]
     let battery_status = if state.battery_charging {
-        "⚡ En charge"
+        icons.label(Icon::Charging, "En charge")
     } else {
-        "🔋 Sur batterie"
+        icons.label(Icon::Battery, "Sur batterie")
     };
 
+    // Grille par cœur, suivie des températures quand les capteurs les exposent
+    let core_grid = state
+        .core_usages
+        .iter()
+        .enumerate()
+        .fold(row![].spacing(6), |grid, (index, usage)| {
+            grid.push(core_badge(format!("C{} {:.0} %", index, usage)))
+        })
+        .wrap()
+        .vertical_spacing(6);
+
+    #[cfg(feature = "temperature")]
+    let core_grid = column![
+        core_grid,
+        state
+            .core_temperatures
+            .iter()
+            .fold(row![].spacing(6), |grid, (label, temperature)| {
+                grid.push(core_badge(format!("{} {:.0} °C", label, temperature)))
+            })
+            .wrap()
+            .vertical_spacing(6),
+    ]
+    .spacing(6);
+
+    #[cfg(feature = "processes")]
+    let process_text = state.config.enabled.processes.then(|| {
+        let mut process_text = if state.thread_count > 0 {
+            format!("{} processus · {} threads", state.process_count, state.thread_count)
+        } else {
+            format!("{} processus", state.process_count)
+        };
+        // Lancés / terminés depuis le relevé précédent de la liste (locale uniquement)
+        if state.remote.is_none() {
+            let (started, exited) = state.process_churn;
+            process_text.push_str(&format!(" · +{} / -{}", started, exited));
+        }
+        process_text
+    });
+    #[cfg(not(feature = "processes"))]
+    let process_text: Option<String> = None;
+
+    // Un écart-type élevé signale une charge en rafales, même à moyenne modérée
+    #[cfg(not(feature = "minimal"))]
+    let cpu_spread = Some(
+        text(format!("σ {:.1}", std_dev(&state.charts.cpu)))
+            .size(14)
+            .color(Color::from_rgba8(255, 255, 255, 0.8)),
+    );
+    #[cfg(feature = "minimal")]
+    let cpu_spread: Option<Element<'_, Message>> = None;
+
     let cpu_card = create_card(
-        "💻 PROCESSEUR",
-        Color::from_rgb8(0x3b, 0x82, 0xf6),
+        icons.label(Icon::Cpu, "PROCESSEUR"),
+        accent,
         column![
             text(format!("{:.1} %", cpu_percent))
                 .size(32)
                 .color(Color::WHITE),
             progress_bar(0.0..=100.0, cpu_percent),
-            text("Historique (2 min)")
+            core_grid,
+            cpu_spread,
+            // Nombre de cœurs réellement sollicités : reflète le parallélisme de la charge
+            (!state.core_usages.is_empty()).then(|| {
+                text(format!(
+                    "{}/{} cœurs occupés",
+                    busy_core_count(&state.core_usages, state.config.busy_core_threshold),
+                    state.core_usages.len()
+                ))
                 .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            cpu_chart
+                .color(Color::from_rgba8(255, 255, 255, 0.8))
+            }),
+            process_text.map(|counts| {
+                text(counts)
+                    .size(14)
+                    .color(Color::from_rgba8(255, 255, 255, 0.8))
+            }),
+            history_label(),
+            cpu_chart,
+            // Signal plus stable que le pourcentage, mesuré localement uniquement
+            (cfg!(unix) && state.remote.is_none()).then(|| {
+                let (one, five, fifteen) = state.load_average;
+                text(format!("Charge {:.2} · {:.2} · {:.2} (1, 5, 15 min)", one, five, fifteen))
+                    .size(12)
+                    .color(Color::from_rgba8(255, 255, 255, 0.7))
+            }),
         ]
         .spacing(10)
     );
 
-    let ram_card = create_card(
-        "🧠 MÉMOIRE",
-        Color::from_rgb8(0xec, 0x48, 0x99),
+    // La jauge passe à l'orange puis au rouge selon la pression mémoire
+    let memory_level = memory_pressure(state.available_mem_mb, state.total_mem_mb)
+        .map_or(LoadLevel::Normal, |pressure| state.config.memory_thresholds.level(pressure));
+
+    // Mémoire totale nulle (certains conteneurs) : pas de jauge ni de graphique à 0 %
+    let ram_content = if memory_available(state.total_mem_mb) {
         column![
             text(format!("{:.1} %", ram_percent))
                 .size(32)
                 .color(Color::WHITE),
-            progress_bar(0.0..=100.0, ram_percent),
+            progress_bar(0.0..=100.0, ram_percent).style(move |theme: &Theme| {
+                let style = progress_bar::primary(theme);
+                match memory_level {
+                    LoadLevel::Normal => style,
+                    level => {
+                        let [r, g, b] = level.rgb();
+                        progress_bar::Style {
+                            bar: Color::from_rgb8(r, g, b).into(),
+                            ..style
+                        }
+                    }
+                }
+            }),
             text(ram_text)
                 .size(14)
                 .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            text("Historique (2 min)")
-                .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
+            history_label(),
             ram_chart
         ]
-        .spacing(10)
+    } else {
+        column![text("Métriques mémoire indisponibles")
+            .size(18)
+            .color(Color::WHITE)]
+    };
+
+    let ram_card = create_card(
+        icons.label(Icon::Memory, "MÉMOIRE"),
+        Color::from_rgb8(0xec, 0x48, 0x99),
+        ram_content.spacing(10),
     );
 
+    // Répartition IPv4 / IPv6 approximative, affichée seulement si disponible
     #[cfg(feature = "network")]
-    let network_card = create_card(
-        "🌐 RÉSEAU",
-        Color::from_rgb8(0x10, 0xb9, 0x81),
+    let ip_split_text: Option<String> = {
+        #[cfg(feature = "netstat")]
+        {
+            state.ip_version_bytes.map(|(ipv4, ipv6)| {
+                format!(
+                    "≈ IPv4 {:.2} GiB · IPv6 {:.2} GiB (approximation)",
+                    ipv4 as f32 / 1_073_741_824.0,
+                    ipv6 as f32 / 1_073_741_824.0,
+                )
+            })
+        }
+        #[cfg(not(feature = "netstat"))]
+        {
+            None
+        }
+    };
+
+    // Légende : l'interface à l'origine du trafic si elle en représente plus de 80 %
+    // (inconnue en mode distant)
+    #[cfg(feature = "network")]
+    let net_legend = state.remote.is_none().then(|| match &state.top_interface {
+        Some((name, share)) if *share > State::DOMINANT_SHARE => text(format!(
+            "Source : {} ({:.0} %)",
+            name,
+            share * 100.0
+        ))
+        .size(13)
+        .color(Color::WHITE),
+        Some(_) => text("Source : mixte")
+            .size(13)
+            .color(Color::from_rgba8(255, 255, 255, 0.7)),
+        None => text("Source : aucun trafic")
+            .size(13)
+            .color(Color::from_rgba8(255, 255, 255, 0.7)),
+    });
+
+    // Taux d'utilisation du lien, sur une échelle fixe de 0 à 100 %
+    #[cfg(feature = "network")]
+    let utilization_section = state.net_utilization().map(|utilization| {
+        let utilization = state.displayed("net_utilization", utilization);
+        #[cfg(not(feature = "minimal"))]
+        let utilization_chart = Some(
+            Canvas::new(
+                Sparkline::new(&state.charts.utilization, 100.0, &state.charts.caches.utilization)
+                    .fill(state.config.chart_fill)
+                    .smoothing(state.config.smoothing)
+                    .unit("%"),
+            )
+            .height(Pixels(60.0))
+            .width(Length::Fill),
+        );
+        #[cfg(feature = "minimal")]
+        let utilization_chart: Option<Element<'_, Message>> = None;
+
         column![
-            row![
+            text(format!(
+                "Utilisation du lien : {:.0} % (↓{:.1} ↑{:.1} Mbps)",
+                utilization, down_mbps, up_mbps
+            ))
+            .size(14)
+            .color(Color::from_rgba8(255, 255, 255, 0.8)),
+            progress_bar(0.0..=100.0, utilization),
+            utilization_chart,
+        ]
+        .spacing(8)
+    });
+
+    // Détail par interface, les moins actives étant regroupées sous « autres »
+    #[cfg(feature = "network")]
+    let interface_list = state
+        .interface_rates
+        .iter()
+        .map(|(name, down, up)| (name.as_str(), *down, *up))
+        .chain(state.other_interfaces_rate.map(|(down, up)| ("autres", down, up)))
+        .fold(column![].spacing(2), |list, (name, down, up)| {
+            let label = match classify_interface(name).icon() {
+                Some(icon) => icons.label(icon, name),
+                None => name.to_string(),
+            };
+            // Petite courbe par interface pour repérer celle qui est vraiment active
+            #[cfg(not(feature = "minimal"))]
+            let chart = state.charts.interfaces.get(name).map(|chart| {
+                let max_value = chart.history.iter().copied().fold(1.0, f32::max);
+                Canvas::new(
+                    Sparkline::new(&chart.history, max_value, &chart.cache)
+                        .fill(state.config.chart_fill)
+                        .smoothing(state.config.smoothing),
+                )
+                .height(Pixels(24.0))
+                .width(Length::Fill)
+            });
+            #[cfg(feature = "minimal")]
+            let chart: Option<Element<'_, Message>> = None;
+            list.push(
                 column![
-                    text("↓ Téléchargement")
-                        .size(14)
+                    text(format!("{} : ↓{:.2} ↑{:.2} Mbps", label, down, up))
+                        .size(13)
                         .color(Color::from_rgba8(255, 255, 255, 0.8)),
-                    text(format!("{:.2} Mbps", state.down_mbps))
+                    chart,
+                ]
+                .spacing(2),
+            )
+        });
+
+    #[cfg(feature = "network")]
+    let network_card = create_card(
+        icons.label(Icon::Network, "RÉSEAU"),
+        Color::from_rgb8(0x10, 0xb9, 0x81),
+        column![
+            row![
+                column![
+                    text("↓ Téléchargement")
+                        .size(14)
+                        .color(Color::from_rgba8(255, 255, 255, 0.8)),
+                    text(format_rate(down_mbps))
                         .size(24)
                         .color(Color::WHITE),
                 ]
@@ -325,7 +1458,7 @@ fn view(state: &State) -> Element<'_, Message> {
                     text("↑ Upload")
                         .size(14)
                         .color(Color::from_rgba8(255, 255, 255, 0.8)),
-                    text(format!("{:.2} Mbps", state.up_mbps))
+                    text(format_rate(up_mbps))
                         .size(24)
                         .color(Color::WHITE),
                 ]
@@ -333,72 +1466,142 @@ fn view(state: &State) -> Element<'_, Message> {
                 .width(Length::Fill),
             ]
             .spacing(16),
-            text(format!("Total: ↓ {:.2} GiB  ↑ {:.2} GiB", total_rx_gib, total_tx_gib))
-                .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            text("Historique (2 min)")
+            network_totals_gib.map(|(total_rx_gib, total_tx_gib)| {
+                text(format!("Total: ↓ {:.2} GiB  ↑ {:.2} GiB", total_rx_gib, total_tx_gib))
+                    .size(14)
+                    .color(Color::from_rgba8(255, 255, 255, 0.8))
+            }),
+            row![
+                text(format!(
+                    "Pic : ↓{:.0} ↑{:.0} Mb/s",
+                    state.peak_down_mbps, state.peak_up_mbps
+                ))
                 .size(14)
                 .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            net_down_chart,
-            net_up_chart,
+                button(text("Réinitialiser").size(12))
+                    .padding([2, 8])
+                    .on_press(Message::ResetNetworkPeak),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+            ip_split_text.map(|split| {
+                text(split)
+                    .size(12)
+                    .color(Color::from_rgba8(255, 255, 255, 0.7))
+            }),
+            (state.debug && state.remote.is_none()).then(|| {
+                text(state.rate_check_text())
+                    .size(12)
+                    .color(Color::from_rgba8(255, 255, 255, 0.7))
+            }),
+            net_charts,
+            net_legend,
+            utilization_section,
+            interface_list,
         ]
         .spacing(10)
     );
 
     #[cfg(feature = "battery")]
     let battery_card = create_card(
-        "🔋 BATTERIE",
+        icons.label(Icon::Battery, "BATTERIE"),
         battery_color,
         column![
-            text(format!("{:.0} %", state.battery_percent))
+            text(format!("{:.0} %", battery_percent))
                 .size(32)
                 .color(Color::WHITE),
-            progress_bar(0.0..=100.0, state.battery_percent),
+            progress_bar(0.0..=100.0, battery_percent),
             text(battery_status)
                 .size(14)
                 .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            text("Historique (2 min)")
-                .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
+            history_label(),
             battery_chart
         ]
         .spacing(10)
     );
 
-    #[cfg(feature = "disk")]
-    let disk_chart = Canvas::new(Sparkline {
-        data: &state.disk_history,
-        color: Color::from_rgb8(0xFF, 0xFF, 0xFF),
-        max_value: 100.0,
-    })
-    .height(Pixels(80.0))
-    .width(Length::Fill);
+    #[cfg(all(feature = "disk", not(feature = "minimal")))]
+    let disk_chart = Some(
+        Canvas::new(metric_sparkline(state, builtin_metric("disk"), &state.charts.disk, &state.charts.caches.disk))
+            .height(Pixels(80.0))
+            .width(Length::Fill),
+    );
+    #[cfg(all(feature = "disk", feature = "minimal"))]
+    let disk_chart: Option<Element<'_, Message>> = None;
+
+    // Activité du disque (lecture + écriture), à l'échelle de son propre maximum
+    #[cfg(all(feature = "disk", not(feature = "minimal")))]
+    let disk_io_chart = Some(
+        Canvas::new(
+            Sparkline::new(
+                &state.charts.disk_io,
+                state.charts.disk_io.iter().copied().fold(1.0_f32, f32::max),
+                &state.charts.caches.disk_io,
+            )
+            .fill(state.config.chart_fill)
+            .smoothing(state.config.smoothing)
+            .unit("Mo/s")
+            .guides(),
+        )
+        .height(Pixels(60.0))
+        .width(Length::Fill),
+    );
+    #[cfg(all(feature = "disk", feature = "minimal"))]
+    let disk_io_chart: Option<Element<'_, Message>> = None;
 
     #[cfg(feature = "disk")]
     let disk_card = create_card(
-        "💾 STOCKAGE",
+        icons.label(Icon::Disk, "STOCKAGE"),
         Color::from_rgb8(0xf5, 0x9e, 0x0b),
         column![
-            text(format!("{:.0} %", state.disk_percent))
+            text(format!("{:.0} %", disk_percent))
                 .size(32)
                 .color(Color::WHITE),
-            progress_bar(0.0..=100.0, state.disk_percent),
-            text(format!("{} / {} Go", state.disk_used_gb, state.disk_total_gb))
-                .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            text("Historique (2 min)")
-                .size(14)
-                .color(Color::from_rgba8(255, 255, 255, 0.8)),
-            disk_chart
+            progress_bar(0.0..=100.0, disk_percent),
+            row![
+                text(format!("{} / {}", format_gb(state.disk_used_gb), format_gb(state.disk_total_gb)))
+                    .size(14)
+                    .color(Color::from_rgba8(255, 255, 255, 0.8))
+                    .width(Length::Fill),
+                button(
+                    text(match state.config.disk_scope {
+                        DiskScope::All => "Tous les volumes",
+                        DiskScope::System => "Volume système",
+                    })
+                    .size(12)
+                )
+                .padding([4, 10])
+                .style(|_theme: &Theme, _status| button::Style {
+                    background: Some(Color::from_rgba8(255, 255, 255, 0.2).into()),
+                    border: Border {
+                        radius: 8.0.into(),
+                        ..Default::default()
+                    },
+                    text_color: Color::WHITE,
+                    ..Default::default()
+                })
+                .on_press(Message::ToggleDiskScope),
+            ]
+            .align_y(iced::Alignment::Center),
+            history_label(),
+            disk_chart,
+            text(format!(
+                "E/S : lecture {:.1} Mo/s · écriture {:.1} Mo/s",
+                state.disk_read_mb_s, state.disk_write_mb_s
+            ))
+            .size(14)
+            .color(Color::from_rgba8(255, 255, 255, 0.8)),
+            disk_io_chart
         ]
         .spacing(10)
     );
 
     // Créer les boutons d'onglets
     let tabs = row![
-        create_tab_button("Système", Tab::System, state.current_tab),
-        create_tab_button("Réseau", Tab::Network, state.current_tab),
-        create_tab_button("Énergie", Tab::Power, state.current_tab),
+        create_tab_button("Système".to_string(), Tab::System, state.current_tab, accent, TabSize::Regular, Message::TabSelected),
+        create_tab_button("Réseau".to_string(), Tab::Network, state.current_tab, accent, TabSize::Regular, Message::TabSelected),
+        create_tab_button("Énergie".to_string(), Tab::Power, state.current_tab, accent, TabSize::Regular, Message::TabSelected),
+        create_tab_button(icons.label(Icon::Settings, "Réglages"), Tab::Settings, state.current_tab, accent, TabSize::Regular, Message::TabSelected),
     ]
     .spacing(10);
 
@@ -415,35 +1618,46 @@ fn view(state: &State) -> Element<'_, Message> {
             .spacing(20);
 
             #[cfg(feature = "disk")]
-            {
+            if state.config.enabled.disk {
                 cards = cards.push(
                     row![container(disk_card).width(Length::Fill)].spacing(20)
                 );
             }
 
+            #[cfg(feature = "processes")]
+            if state.config.enabled.processes && state.remote.is_none() {
+                cards = cards.push(disk_writers_card(state));
+            }
+
+            #[cfg(not(feature = "minimal"))]
+            {
+                if !state.charts.custom.is_empty() {
+                    cards = cards.push(custom_charts_card(state));
+                }
+
+                if let Some(pair) = &state.charts.correlation {
+                    cards = cards.push(correlation_card(state, pair));
+                }
+            }
+
+            cards = cards.push(comparison_card(state));
             cards
         }
         Tab::Network => {
             let mut cards = column![];
 
             #[cfg(feature = "network")]
-            {
+            if state.config.enabled.network {
                 cards = cards.push(
                     row![container(network_card).width(Length::Fill)].spacing(20)
                 );
+            } else {
+                cards = cards.push(disabled_placeholder("Module réseau désactivé"));
             }
 
             #[cfg(not(feature = "network"))]
             {
-                cards = cards.push(
-                    container(
-                        text("Module réseau non activé")
-                            .size(24)
-                            .color(Color::from_rgb8(0x6b, 0x7c, 0x93))
-                    )
-                    .padding(60)
-                    .center(Length::Fill)
-                );
+                cards = cards.push(disabled_placeholder("Module réseau non activé"));
             }
 
             cards.spacing(20)
@@ -452,33 +1666,163 @@ fn view(state: &State) -> Element<'_, Message> {
             let mut cards = column![];
 
             #[cfg(feature = "battery")]
-            {
+            if state.config.enabled.battery {
                 cards = cards.push(
                     row![container(battery_card).width(Length::Fill)].spacing(20)
                 );
+            } else {
+                cards = cards.push(disabled_placeholder("Module batterie désactivé"));
             }
 
             #[cfg(not(feature = "battery"))]
             {
-                cards = cards.push(
-                    container(
-                        text("Module batterie non activé")
-                            .size(24)
-                            .color(Color::from_rgb8(0x6b, 0x7c, 0x93))
-                    )
-                    .padding(60)
-                    .center(Length::Fill)
-                );
+                cards = cards.push(disabled_placeholder("Module batterie non activé"));
             }
 
             cards.spacing(20)
         }
+        Tab::Settings => settings_view(state),
     };
 
+    let toast = state.toast.as_ref().map(|(message, _)| {
+        container(text(message.as_str()).size(14).color(Color::WHITE))
+            .padding([6, 12])
+            .style(|_theme: &Theme| container::Style {
+                background: Some(Color::from_rgba8(0x1f, 0x29, 0x37, 0.85).into()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+    });
+
+    let remote_badge = state.remote.as_ref().map(|remote| {
+        let (label, background) = if remote.connected {
+            (remote.target.clone(), Color::from_rgb8(0x10, 0xb9, 0x81))
+        } else {
+            (format!("{} · déconnecté", remote.target), Color::from_rgb8(0x6b, 0x72, 0x80))
+        };
+
+        container(text(label).size(14).color(Color::WHITE))
+            .padding([6, 12])
+            .style(move |_theme: &Theme| container::Style {
+                background: Some(background.into()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+    });
+
+    let paused_badge = state.paused.then(|| {
+        container(text(icons.label(Icon::Pause, "EN PAUSE")).size(14).color(Color::WHITE))
+            .padding([6, 12])
+            .style(|_theme: &Theme| container::Style {
+                background: Some(Color::from_rgb8(0xef, 0x44, 0x44).into()),
+                border: Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+    });
+
+    let unusual = state.unusual_metrics();
+    let unusual_badge = (!unusual.is_empty()).then(|| {
+        let background = Color::from_rgb8(0xf5, 0x9e, 0x0b);
+        container(
+            text(format!("Inhabituel : {}", unusual.join(", ")))
+                .size(14)
+                .color(contrast_color(background)),
+        )
+        .padding([6, 12])
+        .style(move |_theme: &Theme| container::Style {
+            background: Some(background.into()),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    });
+
+    let errors_badge = (!state.errors.is_empty()).then(|| {
+        let mut errors: Vec<_> = state.errors.iter().collect();
+        errors.sort();
+        let details = errors
+            .iter()
+            .map(|(source, err)| format!("{} : {}", source, err))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        tooltip(
+            container(text("⚠").size(16).color(Color::WHITE))
+                .padding([4, 10])
+                .style(|_theme: &Theme| container::Style {
+                    background: Some(Color::from_rgb8(0xef, 0x44, 0x44).into()),
+                    border: Border {
+                        radius: 8.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            container(text(details).size(13))
+                .padding(8)
+                .style(container::rounded_box),
+            tooltip::Position::Bottom,
+        )
+    });
+
+    let stale_banner = state.stale_for(Instant::now()).map(|age| {
+        container(
+            text(format!(
+                "Données périmées : dernière mise à jour il y a {} s",
+                age.as_secs()
+            ))
+            .size(14)
+            .color(Color::from_rgb8(0x92, 0x40, 0x0e)),
+        )
+        .width(Length::Fill)
+        .padding([8, 12])
+        .style(|_theme: &Theme| container::Style {
+            background: Some(Color::from_rgb8(0xfe, 0xf3, 0xc7).into()),
+            border: Border {
+                color: Color::from_rgb8(0xf5, 0x9e, 0x0b),
+                width: 1.0,
+                radius: 8.0.into(),
+            },
+            ..Default::default()
+        })
+    });
+
     let content = column![
-        text("⚡ Moniteur Système")
-            .size(40)
-            .color(Color::from_rgb8(0x1f, 0x29, 0x37)),
+        row![
+            text(icons.label(Icon::App, "Moniteur Système"))
+                .size(40)
+                .color(Color::from_rgb8(0x1f, 0x29, 0x37))
+                .width(Length::Fill),
+            remote_badge,
+            toast,
+            unusual_badge,
+            errors_badge,
+            paused_badge,
+            // Fige les graphiques pour lire un pic (aussi avec Espace)
+            choice_button(
+                accent,
+                if state.paused {
+                    "Reprendre".to_string()
+                } else {
+                    icons.label(Icon::Pause, "Pause")
+                },
+                state.paused,
+                Message::TogglePause,
+            ),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center),
+        stale_banner,
         tabs,
         content_cards
     ]
@@ -496,7 +1840,209 @@ fn view(state: &State) -> Element<'_, Message> {
     .into()
 }
 
-fn create_card<'a>(title: &'a str, bg_color: Color, content: iced::widget::Column<'a, Message>) -> Element<'a, Message> {
+// Mesure avant/après : « Marquer » fige un instantané, « Comparer » affiche
+// l'écart avec les valeurs actuelles
+fn comparison_card(state: &State) -> Element<'_, Message> {
+    let buttons = row![
+        button(text("Marquer").size(14))
+            .padding([6, 14])
+            .on_press(Message::MarkSnapshot),
+        button(text("Comparer").size(14))
+            .padding([6, 14])
+            .on_press_maybe(state.marked_snapshot.is_some().then_some(Message::CompareSnapshot)),
+    ]
+    .spacing(10);
+
+    let status = match (&state.marked_snapshot, &state.comparison) {
+        (_, Some((delta, elapsed))) => comparison_lines(delta, *elapsed),
+        (Some((_, marked_at)), None) => vec![format!(
+            "Instantané marqué il y a {} s",
+            marked_at.elapsed().as_secs()
+        )],
+        (None, None) => vec!["Aucun instantané marqué".to_string()],
+    };
+
+    let lines = status.into_iter().fold(column![].spacing(4), |lines, line| {
+        lines.push(text(line).size(14).color(Color::from_rgba8(255, 255, 255, 0.9)))
+    });
+
+    create_card(
+        "Δ AVANT / APRÈS".to_string(),
+        Color::from_rgb8(0x47, 0x55, 0x69),
+        column![buttons, lines].spacing(12),
+    )
+}
+
+fn comparison_lines(delta: &SnapshotDelta, elapsed: Duration) -> Vec<String> {
+    let mut lines = vec![
+        format!("Écart sur {} s", elapsed.as_secs()),
+        format!("CPU : {:+.1} pts · RAM : {:+.1} pts", delta.cpu, delta.ram_percent),
+    ];
+
+    if let (Some(rx), Some(tx)) = (delta.rx_bytes, delta.tx_bytes) {
+        lines.push(format!("Réseau : ↓ {} ↑ {}", format_bytes(rx), format_bytes(tx)));
+    }
+    if let Some(disk_used_gb) = delta.disk_used_gb {
+        lines.push(format!("Stockage : {:+.1} Go", disk_used_gb));
+    }
+    if let Some(process_count) = delta.process_count {
+        lines.push(format!("Processus : {:+}", process_count));
+    }
+
+    lines
+}
+
+// Définition d'un graphique intégré, toujours présente dans le registre
+#[cfg(not(feature = "minimal"))]
+fn builtin_metric(name: &str) -> &'static MetricDef {
+    find_metric(name).expect("graphique intégré absent du registre METRICS")
+}
+
+// Courbe d'une métrique du registre : unité et échelle viennent de sa définition,
+// `max_value: None` suivant le maximum affiché
+#[cfg(not(feature = "minimal"))]
+fn metric_sparkline<'a>(
+    state: &'a State,
+    metric: &'static MetricDef,
+    history: &'a History,
+    cache: &'a Cache,
+) -> Sparkline<'a> {
+    let max_value = metric
+        .max_value
+        .unwrap_or_else(|| history.iter().copied().fold(1.0_f32, f32::max));
+    Sparkline::new(history, max_value, cache)
+        .baseline(state.config.chart_baseline)
+        .fill(state.config.chart_fill)
+        .smoothing(state.config.smoothing)
+        .unit(metric.unit)
+}
+
+// Graphiques déclarés dans la configuration, construits depuis le registre des métriques
+#[cfg(not(feature = "minimal"))]
+fn custom_charts_card(state: &State) -> Element<'_, Message> {
+    let charts = state.charts.custom.iter().zip(&state.charts.caches.custom).fold(
+        column![].spacing(12),
+        |charts, ((metric, history), cache)| {
+            let current = match history.iter().next_back() {
+                Some(value) => format!("{} : {:.1} {}", metric.label, value, metric.unit),
+                None => format!("{} : indisponible", metric.label),
+            };
+
+            charts.push(column![
+                text(current).size(14).color(Color::WHITE),
+                Canvas::new(metric_sparkline(state, metric, history, cache))
+                    .height(Pixels(60.0))
+                    .width(Length::Fill),
+            ]
+            .spacing(4))
+        },
+    );
+
+    create_card(
+        "GRAPHIQUES".to_string(),
+        Color::from_rgb8(0x47, 0x55, 0x69),
+        column![
+            charts,
+            text(state.history_label())
+                .size(14)
+                .color(Color::from_rgba8(255, 255, 255, 0.8)),
+        ]
+        .spacing(10),
+    )
+}
+
+// Processus écrivant le plus sur disque depuis le dernier relevé de la liste
+#[cfg(feature = "processes")]
+fn disk_writers_card(state: &State) -> Element<'_, Message> {
+    let rows = if state.disk_writers.is_empty() {
+        column![text("Aucune écriture depuis le dernier relevé")
+            .size(14)
+            .color(Color::from_rgba8(255, 255, 255, 0.8))]
+    } else {
+        state
+            .disk_writers
+            .iter()
+            .fold(column![].spacing(4), |rows, (name, pid, rate)| {
+                rows.push(row![
+                    text(format!("{} ({})", name, pid))
+                        .size(14)
+                        .color(Color::WHITE)
+                        .width(Length::Fill),
+                    text(format!("{:.2} Mo/s", rate)).size(14).color(Color::WHITE),
+                ])
+            })
+    };
+
+    create_card(
+        state.config.icons.label(Icon::Disk, "ÉCRITURES DISQUE"),
+        Color::from_rgb8(0x64, 0x74, 0x8b),
+        column![rows].spacing(10),
+    )
+}
+
+fn core_badge(label: String) -> Element<'static, Message> {
+    container(text(label).size(12).color(Color::WHITE))
+        .padding([2, 6])
+        .style(|_theme: &Theme| container::Style {
+            background: Some(Color::from_rgba8(255, 255, 255, 0.15).into()),
+            border: Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
+
+// Deux métriques superposées, chacune ramenée à son propre maximum
+#[cfg(not(feature = "minimal"))]
+fn correlation_card<'a>(
+    state: &'a State,
+    [(first, first_history), (second, second_history)]: &'a [(&'static MetricDef, History); 2],
+) -> Element<'a, Message> {
+    const FIRST_COLOR: Color = Color::WHITE;
+    const SECOND_COLOR: Color = Color::from_rgb(0.98, 0.75, 0.14);
+
+    let legend_entry = |metric: &MetricDef, color: Color| {
+        row![
+            container(text(""))
+                .width(Pixels(10.0))
+                .height(Pixels(10.0))
+                .style(move |_theme: &Theme| container::Style {
+                    background: Some(color.into()),
+                    border: Border { radius: 2.0.into(), ..Default::default() },
+                    ..Default::default()
+                }),
+            text(metric.label).size(12).color(Color::WHITE),
+        ]
+        .spacing(6)
+        .align_y(iced::Alignment::Center)
+    };
+
+    create_card(
+        "CORRÉLATION".to_string(),
+        Color::from_rgb8(0x47, 0x55, 0x69),
+        column![
+            row![legend_entry(first, FIRST_COLOR), legend_entry(second, SECOND_COLOR)].spacing(16),
+            Canvas::new(CorrelationChart {
+                first: first_history,
+                second: second_history,
+                first_color: FIRST_COLOR,
+                second_color: SECOND_COLOR,
+                smoothing: state.config.smoothing,
+                cache: &state.charts.caches.correlation,
+            })
+            .height(Pixels(80.0))
+            .width(Length::Fill),
+            text(format!("Chaque courbe à sa propre échelle · {}", state.history_label()))
+                .size(14)
+                .color(Color::from_rgba8(255, 255, 255, 0.8)),
+        ]
+        .spacing(10),
+    )
+}
+
+fn create_card<'a>(title: String, bg_color: Color, content: iced::widget::Column<'a, Message>) -> Element<'a, Message> {
     container(
         column![
             text(title)
@@ -526,140 +2072,1134 @@ fn create_card<'a>(title: &'a str, bg_color: Color, content: iced::widget::Colum
     .into()
 }
 
-fn create_tab_button(label: &'static str, tab: Tab, current_tab: Tab) -> Element<'static, Message> {
-    let is_active = tab == current_tab;
-    
-    button(
+fn settings_view(state: &State) -> iced::widget::Column<'_, Message> {
+    let config = &state.config;
+    let accent = state.accent();
+
+    let interval_choices = State::INTERVAL_CHOICES.iter().fold(row![].spacing(6), |choices, &ms| {
+        choices.push(choice_button(
+            accent,
+            format_interval(ms),
+            config.refresh_ms == ms,
+            Message::SetInterval(ms),
+        ))
+    });
+
+    // Un intervalle trop court (fichier de configuration) est relevé au minimum de sysinfo
+    let effective_ms = config.effective_refresh_ms();
+    let interval_control = row![
+        interval_choices,
+        (effective_ms != config.refresh_ms).then(|| {
+            text(format!(
+                "effectif : {} (demandé : {})",
+                format_interval(effective_ms),
+                format_interval(config.refresh_ms)
+            ))
+            .size(14)
+            .color(Color::from_rgb8(0xb4, 0x53, 0x09))
+        }),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    #[cfg(not(feature = "minimal"))]
+    let history_stepper = row![
+        choice_button(
+            accent,
+            "−".to_string(),
+            false,
+            Message::SetHistoryLen(config.history_len.saturating_sub(State::HISTORY_STEP).max(State::HISTORY_STEP)),
+        ),
+        text(format!("{} points", config.history_len))
+            .size(16)
+            .color(Color::from_rgb8(0x1f, 0x29, 0x37)),
+        choice_button(
+            accent,
+            "+".to_string(),
+            false,
+            Message::SetHistoryLen((config.history_len + State::HISTORY_STEP).min(State::HISTORY_MAX)),
+        ),
+    ]
+    .spacing(10)
+    .align_y(iced::Alignment::Center);
+
+    let mut rows = column![settings_row("Intervalle de rafraîchissement", interval_control.into())].spacing(12);
+
+    // Réglages des graphiques et de l'historique, absents du profil `minimal`
+    #[cfg(not(feature = "minimal"))]
+    {
+        rows = rows
+            .push(settings_row("Longueur de l'historique", history_stepper.into()))
+            .push(settings_row(
+                "Réduire les animations",
+                choice_button(accent, on_off(config.reduce_motion), config.reduce_motion, Message::ToggleReduceMotion),
+            ))
+            .push(settings_row(
+                "Graphiques : base au minimum des données",
+                choice_button(
+                    accent,
+                    on_off(config.chart_baseline == BaselineMode::Min),
+                    config.chart_baseline == BaselineMode::Min,
+                    Message::ToggleChartBaseline,
+                ),
+            ));
+    }
+
+    let icon_choices = [
+        ("Emoji", IconSet::Emoji),
+        ("ASCII", IconSet::Ascii),
+        ("Aucune", IconSet::None),
+    ]
+    .into_iter()
+    .fold(row![].spacing(6), |choices, (label, icons)| {
+        choices.push(choice_button(
+            accent,
+            label.to_string(),
+            config.icons == icons,
+            Message::SetIconSet(icons),
+        ))
+    });
+    rows = rows.push(settings_row("Icônes", icon_choices.into()));
+    #[cfg(not(feature = "minimal"))]
+    {
+        rows = rows.push(settings_row(
+            "Référence CPU / réseau",
+            row![
+                text(state.baseline_label())
+                    .size(14)
+                    .color(Color::from_rgb8(0x6b, 0x7c, 0x93)),
+                choice_button(accent, "Apprendre".to_string(), false, Message::LearnBaseline),
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center)
+            .into(),
+        ));
+    }
+    rows = rows.push(settings_row(
+        "Historiques, pics et références",
+        choice_button(accent, "Tout réinitialiser".to_string(), false, Message::ResetAll),
+    ));
+    #[cfg(not(feature = "minimal"))]
+    {
+        rows = rows.push(settings_row(
+            "Historique des graphiques",
+            choice_button(
+                accent,
+                "Exporter en CSV".to_string(),
+                false,
+                Message::ExportHistory(history_export_path()),
+            ),
+        ));
+    }
+
+    #[cfg(feature = "disk")]
+    {
+        rows = rows.push(settings_row(
+            "Stockage : volume système uniquement",
+            choice_button(
+                accent,
+                on_off(config.disk_scope == DiskScope::System),
+                config.disk_scope == DiskScope::System,
+                Message::ToggleDiskScope,
+            ),
+        ));
+    }
+
+    let modules: [(&str, Module, bool); 4] = [
+        ("Module réseau", Module::Network, cfg!(feature = "network")),
+        ("Module batterie", Module::Battery, cfg!(feature = "battery")),
+        ("Module stockage", Module::Disk, cfg!(feature = "disk")),
+        ("Module processus", Module::Processes, cfg!(feature = "processes")),
+    ];
+
+    // Seuls les modules compilés peuvent être activés à l'exécution
+    for (label, module, compiled) in modules {
+        if compiled {
+            let enabled = config.enabled.is_enabled(module);
+            rows = rows.push(settings_row(
+                label,
+                choice_button(accent, on_off(enabled), enabled, Message::ToggleModule(module)),
+            ));
+        }
+    }
+
+    column![container(rows)
+        .padding(24)
+        .width(Length::Fill)
+        .style(|_theme: &Theme| container::Style {
+            background: Some(Color::WHITE.into()),
+            border: Border {
+                radius: 16.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })]
+}
+
+fn settings_row<'a>(label: &'a str, control: Element<'a, Message>) -> Element<'a, Message> {
+    row![
         text(label)
-            .size(18)
-            .color(if is_active {
-                Color::WHITE
-            } else {
-                Color::from_rgb8(0x6b, 0x7c, 0x93)
-            })
-    )
-    .padding([12, 24])
-    .style(move |_theme: &Theme, _status| {
-        button::Style {
+            .size(16)
+            .color(Color::from_rgb8(0x1f, 0x29, 0x37))
+            .width(Length::Fill),
+        control
+    ]
+    .spacing(20)
+    .align_y(iced::Alignment::Center)
+    .into()
+}
+
+fn on_off(value: bool) -> String {
+    if value { "Activé" } else { "Désactivé" }.to_string()
+}
+
+fn choice_button(accent: Color, label: String, is_active: bool, message: Message) -> Element<'static, Message> {
+    button(text(label).size(14))
+        .padding([6, 14])
+        .style(move |_theme: &Theme, _status| button::Style {
             background: Some(if is_active {
-                Color::from_rgb8(0x3b, 0x82, 0xf6).into()
+                accent.into()
             } else {
                 Color::from_rgb8(0xe5, 0xe7, 0xeb).into()
             }),
             border: Border {
-                radius: 10.0.into(),
+                radius: 8.0.into(),
                 ..Default::default()
             },
             text_color: if is_active {
-                Color::WHITE
+                contrast_color(accent)
             } else {
                 Color::from_rgb8(0x6b, 0x7c, 0x93)
             },
             ..Default::default()
-        }
-    })
-    .on_press(Message::TabSelected(tab))
+        })
+        .on_press(message)
+        .into()
+}
+
+fn disabled_placeholder(label: &'static str) -> Element<'static, Message> {
+    container(
+        text(label)
+            .size(24)
+            .color(Color::from_rgb8(0x6b, 0x7c, 0x93))
+    )
+    .padding(60)
+    .center(Length::Fill)
     .into()
 }
 
 impl State {
-    const HISTORY: usize = 120;
+    const INTERVAL_CHOICES: [u64; 4] = [500, 1_000, 2_000, 5_000];
+    const MIN_INTERVAL_MS: u64 = 250;
+    const MAX_INTERVAL_MS: u64 = 10_000;
+    const TOAST_DURATION: Duration = Duration::from_secs(2);
+    const LOG_FLUSH_TICKS: u64 = 10;
+    #[cfg(not(feature = "minimal"))]
+    const HISTORY_STEP: usize = 30;
+    #[cfg(not(feature = "minimal"))]
+    const HISTORY_MAX: usize = 600;
+    #[cfg(feature = "network")]
+    const INTERFACE_EVAL_TICKS: u64 = 5;
+    #[cfg(feature = "network")]
+    const DOMINANT_SHARE: f32 = 0.8;
+    #[cfg(feature = "processes")]
+    const PROCESS_REFRESH_TICKS: u64 = 5;
+    #[cfg(feature = "processes")]
+    const TOP_DISK_WRITERS: usize = 5;
+    #[cfg(not(feature = "minimal"))]
+    const REDUCED_MOTION_TICKS: u64 = 5;
+
+    // Nombre de ticks entre deux points ajoutés aux graphiques
+    #[cfg(not(feature = "minimal"))]
+    fn chart_period_ticks(&self) -> u64 {
+        let motion_ticks = if self.config.reduce_motion {
+            Self::REDUCED_MOTION_TICKS
+        } else {
+            1
+        };
+        let point_ticks = self
+            .config
+            .chart_point_ms
+            .map_or(1, |point_ms| point_ms.div_ceil(self.config.effective_refresh_ms()).max(1));
 
-    fn push_samples(&mut self) {
-        self.cpu_history.push(self.cpu);
-        Self::trim_history(&mut self.cpu_history);
+        motion_ticks.max(point_ticks)
+    }
 
-        let ram_percent = if self.total_mem_mb > 0 {
-            (self.used_mem_mb as f32 / self.total_mem_mb as f32) * 100.0
-        } else {
-            0.0
+    // Âge des valeurs affichées si aucune collecte n'a abouti depuis
+    // `stale_after_intervals` intervalles (jamais en pause)
+    fn stale_for(&self, now: Instant) -> Option<Duration> {
+        let threshold = Duration::from_millis(
+            self.config.effective_refresh_ms() * u64::from(self.config.stale_after_intervals.max(1)),
+        );
+        let age = now.duration_since(self.last_update);
+        (!self.paused && age > threshold).then_some(age)
+    }
+
+    // Dernier point de la série `name` lissée (voir `Charts::series`), tel que tracé
+    // par sa courbe ; la mesure instantanée sans lissage ou tant que l'historique est vide
+    #[cfg(not(feature = "minimal"))]
+    fn displayed(&self, name: &str, current: f32) -> f32 {
+        if self.config.smoothing == Smoothing::None {
+            return current;
+        }
+
+        self.charts
+            .series(name)
+            .and_then(|history| smoothed_latest(history, self.config.smoothing))
+            .unwrap_or(current)
+    }
+
+    // Profil `minimal` : sans historique, la mesure est affichée telle quelle
+    #[cfg(feature = "minimal")]
+    fn displayed(&self, _name: &str, current: f32) -> f32 {
+        current
+    }
+
+    // Écart entre deux points des graphiques
+    #[cfg(not(feature = "minimal"))]
+    fn chart_interval(&self) -> Duration {
+        Duration::from_millis(self.config.effective_refresh_ms() * self.chart_period_ticks())
+    }
+
+    // Tous les historiques en CSV, datés en remontant depuis maintenant
+    #[cfg(not(feature = "minimal"))]
+    fn history_csv(&self) -> String {
+        history_csv(&self.charts.histories(), self.chart_interval(), SystemTime::now())
+    }
+
+    #[cfg(not(feature = "minimal"))]
+    fn saved_history(&self) -> SavedHistory {
+        let mut saved = SavedHistory::new(self.chart_interval(), SystemTime::now());
+        for (name, history) in self.charts.histories() {
+            saved.series.insert(name.to_string(), history.iter().copied().collect());
+        }
+
+        #[cfg(feature = "network")]
+        {
+            saved.counters.insert("peak_down_mbps".to_string(), self.peak_down_mbps);
+            saved.counters.insert("peak_up_mbps".to_string(), self.peak_up_mbps);
+        }
+
+        saved
+    }
+
+    // Reprend l'historique enregistré à la dernière fermeture, sauf s'il est
+    // entièrement sorti de la fenêtre des graphiques
+    #[cfg(not(feature = "minimal"))]
+    fn restore_history(&mut self) {
+        let now = SystemTime::now();
+        let Some(saved) = SavedHistory::load() else {
+            return;
+        };
+        if !saved.is_fresh(self.config.history_len, now) {
+            return;
+        }
+
+        // Le temps passé fermé reste visible : un trou (NaN) d'autant d'échantillons
+        // sépare les données reprises des nouvelles
+        let missed = usize::try_from(saved.missed_samples(now)).unwrap_or(usize::MAX);
+        for (name, history) in self.charts.histories_mut() {
+            for &value in saved.fresh_samples(name, history.capacity(), now) {
+                history.push(value);
+            }
+            for _ in 0..missed.min(history.capacity()) {
+                history.push(f32::NAN);
+            }
+        }
+
+        #[cfg(feature = "network")]
+        {
+            self.peak_down_mbps = saved.counters.get("peak_down_mbps").copied().unwrap_or_default();
+            self.peak_up_mbps = saved.counters.get("peak_up_mbps").copied().unwrap_or_default();
+        }
+    }
+
+    // Métriques au-delà de leur référence apprise (moyenne + k·σ)
+    #[cfg(not(feature = "minimal"))]
+    fn unusual_metrics(&self) -> Vec<&'static str> {
+        let sigmas = self.config.baseline_sigmas;
+        let mut unusual = Vec::new();
+
+        if self.cpu_baseline.exceeds(self.cpu, sigmas) {
+            unusual.push("CPU");
+        }
+
+        #[cfg(feature = "network")]
+        if self.down_baseline.exceeds(self.primary_down_mbps, sigmas) {
+            unusual.push("réseau");
+        }
+
+        unusual
+    }
+
+    // Profil `minimal` : aucune référence à apprendre
+    #[cfg(feature = "minimal")]
+    fn unusual_metrics(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    #[cfg(not(feature = "minimal"))]
+    fn baseline_label(&self) -> String {
+        let Some((cpu_mean, cpu_std_dev)) = self.cpu_baseline.baseline() else {
+            return "non apprise".to_string();
         };
 
-        self.ram_history.push(ram_percent);
-        Self::trim_history(&mut self.ram_history);
+        let label = format!("CPU {:.1} ± {:.1} %", cpu_mean, cpu_std_dev);
 
         #[cfg(feature = "network")]
+        let label = match self.down_baseline.baseline() {
+            Some((down_mean, down_std_dev)) => {
+                format!("{} · ↓ {:.1} ± {:.1} Mb/s", label, down_mean, down_std_dev)
+            }
+            None => label,
+        };
+
+        label
+    }
+
+    fn accent(&self) -> Color {
+        self.config.accent.color(self.system_theme)
+    }
+
+    fn save_config(&mut self) {
+        let result = self.config.save().map_err(|err| err.to_string());
+        self.track_error("configuration", result);
+    }
+
+    // Note l'erreur d'une source (et la journalise), ou l'efface si elle répond de nouveau
+    fn track_error<T>(&mut self, source: &'static str, result: Result<T, String>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.errors.remove(source);
+                Some(value)
+            }
+            Err(err) => {
+                if self.errors.get(source) != Some(&err) {
+                    eprintln!("{} : {}", source, err);
+                }
+                self.errors.insert(source, err);
+                None
+            }
+        }
+    }
+
+    // Compteurs cumulés repris du rafraîchissement `at`, après une pause ou la
+    // réactivation d'un module : ce qui s'est écoulé entre-temps ne doit pas apparaître
+    // comme un pic (réseau, écritures disque) ou une vague de processus au relevé suivant.
+    // Les compteurs disque de sysinfo repartent d'eux-mêmes de ce rafraîchissement.
+    fn rebase_counters(&mut self, sources: &LocalSources, at: Instant) {
+        #[cfg(feature = "network")]
+        if self.config.enabled.network {
+            let allowlist = self.config.interfaces.as_deref();
+            self.rate_tracker.update_for(&sources.networks, allowlist);
+            self.network_total_bytes = Some(network_total_bytes(&sources.networks, allowlist));
+            self.restart_rate_check();
+        }
+        #[cfg(not(feature = "network"))]
+        let _ = sources;
+        #[cfg(feature = "processes")]
+        {
+            self.previous_pids = None;
+            self.last_process_refresh = at;
+        }
+        self.last_tick = at;
+    }
+
+    #[cfg(feature = "disk")]
+    fn refresh_disk_usage(&mut self, disks: &Disks) {
+        let usage = scoped_disk_usage(disks, self.config.disk_scope);
+        self.disk_percent = usage.percent;
+        self.disk_used_gb = usage.used_gb;
+        self.disk_total_gb = usage.total_gb;
+    }
+
+    #[cfg(not(feature = "minimal"))]
+    fn history_label(&self) -> String {
+        let seconds =
+            self.config.history_len as u64 * self.chart_period_ticks() * self.config.effective_refresh_ms() / 1000;
+
+        if seconds < 120 {
+            format!("Historique ({} s)", seconds)
+        } else {
+            format!("Historique ({} min)", seconds / 60)
+        }
+    }
+
+    // Valeurs actuellement affichées, sous forme d'instantané
+    fn snapshot(&self) -> Snapshot {
+        let snapshot = Snapshot {
+            cpu: self.cpu,
+            used_mem_mb: self.used_mem_mb,
+            total_mem_mb: self.total_mem_mb,
+            swap_percent: self.swap_percent,
+            #[cfg(feature = "gpu")]
+            gpu_percent: self.gpu_percent,
+            ..Snapshot::default()
+        };
+
+        #[cfg(feature = "network")]
+        let snapshot = if self.config.enabled.network {
+            Snapshot {
+                down_mbps: Some(self.down_mbps),
+                up_mbps: Some(self.up_mbps),
+                rx_total_bytes: self.network_total_bytes.map(|(rx, _)| rx),
+                tx_total_bytes: self.network_total_bytes.map(|(_, tx)| tx),
+                ..snapshot
+            }
+        } else {
+            snapshot
+        };
+
+        #[cfg(feature = "battery")]
+        let snapshot = if self.config.enabled.battery {
+            Snapshot {
+                battery_percent: Some(self.battery_percent),
+                battery_charging: Some(self.battery_charging),
+                ..snapshot
+            }
+        } else {
+            snapshot
+        };
+
+        #[cfg(feature = "disk")]
+        let snapshot = if self.config.enabled.disk {
+            Snapshot {
+                disk: Some(DiskUsage {
+                    percent: self.disk_percent,
+                    used_gb: self.disk_used_gb,
+                    total_gb: self.disk_total_gb,
+                }),
+                ..snapshot
+            }
+        } else {
+            snapshot
+        };
+
+        #[cfg(feature = "processes")]
+        let snapshot = if self.config.enabled.processes {
+            Snapshot {
+                process_count: Some(self.process_count),
+                thread_count: Some(self.thread_count),
+                ..snapshot
+            }
+        } else {
+            snapshot
+        };
+
+        #[cfg(feature = "temperature")]
+        let snapshot = Snapshot {
+            cpu_temperature: monitor_app::hottest_core(&self.core_temperatures),
+            ..snapshot
+        };
+
+        snapshot
+    }
+
+    // Affiche un instantané reçu d'un hôte distant
+    fn apply_snapshot(&mut self, snapshot: &Snapshot) {
+        self.ticks += 1;
+        self.cpu = snapshot.cpu;
+        self.used_mem_mb = snapshot.used_mem_mb;
+        self.total_mem_mb = snapshot.total_mem_mb;
+        // L'instantané ne transmet pas la mémoire disponible
+        self.available_mem_mb = snapshot.total_mem_mb.saturating_sub(snapshot.used_mem_mb);
+        self.swap_percent = snapshot.swap_percent;
+        #[cfg(feature = "gpu")]
+        {
+            self.gpu_percent = snapshot.gpu_percent;
+        }
+        // Pas de détail par cœur dans l'instantané : seule la température globale est connue
+        self.core_usages.clear();
+        #[cfg(feature = "temperature")]
         {
-            self.down_history.push(self.down_mbps);
-            Self::trim_history(&mut self.down_history);
+            self.core_temperatures = snapshot
+                .cpu_temperature
+                .map(|temperature| vec![("CPU".to_string(), temperature)])
+                .unwrap_or_default();
+        }
 
-            self.up_history.push(self.up_mbps);
-            Self::trim_history(&mut self.up_history);
+        // Ni détail par interface ni répartition IPv4/IPv6 : les graphiques suivent le total
+        #[cfg(feature = "network")]
+        {
+            self.down_mbps = snapshot.down_mbps.unwrap_or_default();
+            self.up_mbps = snapshot.up_mbps.unwrap_or_default();
+            self.primary_interface = None;
+            self.primary_down_mbps = self.down_mbps;
+            self.primary_up_mbps = self.up_mbps;
+            self.top_interface = None;
+            self.interface_rates.clear();
+            self.other_interfaces_rate = None;
+            self.network_total_bytes = snapshot.rx_total_bytes.zip(snapshot.tx_total_bytes);
+            self.record_network_peaks();
+        }
+        #[cfg(feature = "netstat")]
+        {
+            self.ip_version_bytes = None;
         }
 
         #[cfg(feature = "battery")]
         {
-            self.battery_history.push(self.battery_percent);
-            Self::trim_history(&mut self.battery_history);
+            self.battery_percent = snapshot.battery_percent.unwrap_or(100.0);
+            self.battery_charging = snapshot.battery_charging.unwrap_or_default();
         }
 
         #[cfg(feature = "disk")]
+        if let Some(disk) = snapshot.disk {
+            self.disk_percent = disk.percent;
+            self.disk_used_gb = disk.used_gb;
+            self.disk_total_gb = disk.total_gb;
+        }
+
+        #[cfg(feature = "processes")]
         {
-            self.disk_history.push(self.disk_percent);
-            Self::trim_history(&mut self.disk_history);
+            self.process_count = snapshot.process_count.unwrap_or_default();
+            self.thread_count = snapshot.thread_count.unwrap_or_default();
         }
+
+        self.push_samples();
     }
 
-    fn trim_history(history: &mut Vec<f32>) {
-        if history.len() > Self::HISTORY {
-            let extra = history.len() - Self::HISTORY;
-            history.drain(0..extra);
+    // Appelé à chaque mesure : les valeurs sont mises en attente, puis agrégées en un
+    // point par graphique toutes les `chart_period_ticks` mesures
+    #[cfg(not(feature = "minimal"))]
+    fn push_samples(&mut self) {
+        let flush = self.ticks.is_multiple_of(self.chart_period_ticks());
+        let aggregation = self.config.chart_aggregation;
+        // Un tampon par historique, dans l'ordre de `chart_series_mut`
+        let mut pending = std::mem::take(&mut self.charts.pending_points);
+        let series = self.chart_series_mut();
+        pending.resize_with(series.len(), PointBuffer::default);
+        for ((history, value), buffer) in series.into_iter().zip(&mut pending) {
+            if let Some(value) = value {
+                buffer.add(value);
+            }
+            if !flush {
+                continue;
+            }
+            if let Some(point) = buffer.flush(aggregation) {
+                history.push(point);
+            }
         }
-    }
-}
+        self.charts.pending_points = pending;
 
-struct Sparkline<'a> {
-    data: &'a [f32],
-    color: Color,
-    max_value: f32,
-}
+        #[cfg(feature = "network")]
+        self.buffer_interface_rates();
+
+        if !flush {
+            return;
+        }
 
-impl<'a> Program<Message> for Sparkline<'a> {
-    type State = ();
+        self.charts.caches.clear();
 
-    fn draw(
-        &self,
-        _state: &Self::State,
-        renderer: &Renderer,
-        _theme: &Theme,
-        bounds: Rectangle,
-        _cursor: mouse::Cursor,
-    ) -> Vec<Geometry> {
-        let mut frame = Frame::new(renderer, bounds.size());
+        #[cfg(feature = "network")]
+        for chart in self.charts.interfaces.values_mut() {
+            if let Some(point) = chart.pending.flush(aggregation) {
+                chart.history.push(point);
+            }
+            chart.cache.clear();
+        }
+    }
 
-        if self.data.len() < 2 || self.max_value <= 0.0 {
-            return vec![frame.into_geometry()];
+    // Profil `minimal` : aucun point à conserver
+    #[cfg(feature = "minimal")]
+    fn push_samples(&mut self) {}
+
+    // Débit total (↓ + ↑) de chaque interface affichée ; une interface sortie du
+    // classement perd sa courbe, qui repart de zéro si elle y revient
+    #[cfg(all(feature = "network", not(feature = "minimal")))]
+    fn buffer_interface_rates(&mut self) {
+        let len = self.config.history_len;
+        let rates = &self.interface_rates;
+        self.charts
+            .interfaces
+            .retain(|name, _| rates.iter().any(|(shown, _, _)| shown == name));
+
+        for (name, down, up) in rates {
+            self.charts
+                .interfaces
+                .entry(name.clone())
+                .or_insert_with(|| InterfaceChart {
+                    pending: PointBuffer::default(),
+                    history: History::new(len),
+                    cache: Cache::default(),
+                })
+                .pending
+                .add(down + up);
         }
+    }
 
-        let step_x = if self.data.len() > 1 {
-            bounds.width / (self.data.len() as f32 - 1.0)
+    fn ram_percent(&self) -> f32 {
+        if memory_available(self.total_mem_mb) {
+            (self.used_mem_mb as f32 / self.total_mem_mb as f32) * 100.0
         } else {
-            bounds.width
+            0.0
+        }
+    }
+
+    // Une ligne par relevé local, écrite sur disque toutes les `LOG_FLUSH_TICKS`
+    fn log_metrics(&mut self) {
+        #[cfg(feature = "network")]
+        let (down_mbps, up_mbps) = (self.down_mbps, self.up_mbps);
+        #[cfg(not(feature = "network"))]
+        let (down_mbps, up_mbps) = (0.0, 0.0);
+        let timestamp = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let (cpu, ram_percent) = (self.cpu, self.ram_percent());
+        let flush = self.ticks.is_multiple_of(Self::LOG_FLUSH_TICKS);
+
+        let Some(writer) = &mut self.metric_log else {
+            return;
         };
+        let written = writeln!(
+            writer,
+            "{},{:.1},{:.1},{:.3},{:.3}",
+            timestamp, cpu, ram_percent, down_mbps, up_mbps
+        )
+        .and_then(|()| if flush { writer.flush() } else { Ok(()) });
+
+        if let Err(err) = written {
+            eprintln!("Journal des mesures désactivé : {}", err);
+            self.metric_log = None;
+        }
+    }
 
-        let path = Path::new(|builder| {
-            for (i, value) in self.data.iter().enumerate() {
-                let x = i as f32 * step_x;
-                let clamped = value.clamp(0.0, self.max_value);
-                let ratio = if self.max_value > 0.0 {
-                    clamped / self.max_value
-                } else {
-                    0.0
-                };
-                let y = bounds.height - (ratio * bounds.height);
+    // Graphique principal de l'onglet courant : historique, maximum et couleur de sa carte
+    #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+    fn active_chart(&self) -> Option<(&History, f32, Color)> {
+        match self.current_tab {
+            Tab::System => Some((&self.charts.cpu, 100.0, self.accent())),
+            #[cfg(feature = "network")]
+            Tab::Network => Some((
+                &self.charts.down,
+                self.charts.down.iter().copied().fold(1.0_f32, f32::max),
+                Color::from_rgb8(0x10, 0xb9, 0x81),
+            )),
+            #[cfg(feature = "battery")]
+            Tab::Power => Some((
+                &self.charts.battery,
+                100.0,
+                threshold_color(100.0 - self.battery_percent, 50.0, 80.0),
+            )),
+            _ => None,
+        }
+    }
 
-                let point = Point::new(x, y);
+    // Image du graphique de l'onglet, tracée comme dans sa carte (courbe blanche lissée)
+    #[cfg(all(feature = "clipboard-image", not(feature = "minimal")))]
+    fn copy_chart_image(&mut self) -> Result<(), String> {
+        const IMAGE_SIZE: (usize, usize) = (600, 200);
+
+        let (history, max_value, background) = self
+            .active_chart()
+            .filter(|(history, _, _)| history.len() >= 2)
+            .ok_or_else(|| "aucun graphique dans cet onglet".to_string())?;
+        let values = smooth_with_gaps(history, self.config.smoothing);
+        let (width, height) = IMAGE_SIZE;
+        let rgba = rasterize_sparkline(&values, max_value, Color::WHITE, background, width, height);
+
+        let mut clipboard = match self.image_clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => ImageClipboard::new()?,
+        };
+        let copied = clipboard.copy_rgba(rgba, width, height);
+        self.image_clipboard = Some(clipboard);
+        copied
+    }
 
-                if i == 0 {
-                    builder.move_to(point);
-                } else {
-                    builder.line_to(point);
+    // Chaque historique tracé et sa valeur pour le point en cours (`None` : pas de mesure)
+    #[cfg(not(feature = "minimal"))]
+    fn chart_series_mut(&mut self) -> Vec<(&mut History, Option<f32>)> {
+        let ram_percent = self.ram_percent();
+        #[cfg(feature = "network")]
+        let utilization = self.net_utilization();
+        let registry_values: Vec<Option<f32>> =
+            if !self.charts.custom.is_empty() || self.charts.correlation.is_some() {
+                let snapshot = self.snapshot();
+                self.charts
+                    .custom
+                    .iter()
+                    .chain(self.charts.correlation.iter().flatten())
+                    .map(|(metric, _)| (metric.extract)(&snapshot))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        let charts = &mut self.charts;
+        let mut series = vec![
+            (&mut charts.cpu, Some(self.cpu)),
+            (&mut charts.ram, Some(ram_percent)),
+            #[cfg(feature = "network")]
+            (&mut charts.down, Some(self.primary_down_mbps)),
+            #[cfg(feature = "network")]
+            (&mut charts.up, Some(self.primary_up_mbps)),
+            #[cfg(feature = "network")]
+            (&mut charts.utilization, utilization),
+            #[cfg(feature = "network")]
+            (&mut charts.total_down, Some(self.down_mbps)),
+            #[cfg(feature = "network")]
+            (&mut charts.total_up, Some(self.up_mbps)),
+            #[cfg(feature = "battery")]
+            (&mut charts.battery, Some(self.battery_percent)),
+            #[cfg(feature = "disk")]
+            (&mut charts.disk, Some(self.disk_percent)),
+            #[cfg(feature = "disk")]
+            (&mut charts.disk_io, Some(self.disk_read_mb_s + self.disk_write_mb_s)),
+        ];
+        series.extend(
+            charts
+                .custom
+                .iter_mut()
+                .chain(charts.correlation.iter_mut().flatten())
+                .map(|(_, history)| history)
+                .zip(registry_values),
+        );
+        series
+    }
+
+    // Le RateTracker est initialisé au lancement et à la reprise après une pause :
+    // un premier échantillon ne peut donc pas cumuler le trafic depuis le démarrage.
+    #[cfg(feature = "network")]
+    fn record_network_peaks(&mut self) {
+        self.peak_down_mbps = self.peak_down_mbps.max(self.down_mbps);
+        self.peak_up_mbps = self.peak_up_mbps.max(self.up_mbps);
+    }
+
+    // Le trafic d'une pause n'étant pas intégré, le contrôle repart des derniers
+    // compteurs relevés
+    #[cfg(feature = "network")]
+    fn restart_rate_check(&mut self) {
+        if let Some(total_bytes) = self.network_total_bytes {
+            self.session_start_bytes = total_bytes;
+        }
+        self.integrated_bytes = (0.0, 0.0);
+    }
+
+    // Octets comptés par les interfaces depuis le début de la session, face à
+    // l'intégrale des débits : un écart important trahit une remise à zéro des
+    // compteurs ou une erreur sur le temps écoulé
+    #[cfg(feature = "network")]
+    fn rate_check_text(&self) -> String {
+        let (start_rx, start_tx) = self.session_start_bytes;
+        let (total_rx, total_tx) = self
+            .network_total_bytes
+            .unwrap_or(self.session_start_bytes);
+        let counted = (total_rx.saturating_sub(start_rx), total_tx.saturating_sub(start_tx));
+        let (integrated_rx, integrated_tx) = self.integrated_bytes;
+
+        let gap = |integrated: f64, counted: u64| {
+            if counted > 0 {
+                format!("{:+.1} %", (integrated - counted as f64) / counted as f64 * 100.0)
+            } else {
+                "—".to_string()
+            }
+        };
+
+        format!(
+            "Débogage · compteurs ↓{} ↑{} · intégrale des débits ↓{} ↑{} · écart ↓{} ↑{}",
+            format_bytes(counted.0),
+            format_bytes(counted.1),
+            format_bytes(integrated_rx as u64),
+            format_bytes(integrated_tx as u64),
+            gap(integrated_rx, counted.0),
+            gap(integrated_tx, counted.1),
+        )
+    }
+
+    // Utilisation du lien la plus élevée des deux sens, le lien étant full-duplex
+    #[cfg(feature = "network")]
+    fn net_utilization(&self) -> Option<f32> {
+        self.config
+            .net_max_mbps
+            .map(|capacity| net_utilization(self.down_mbps.max(self.up_mbps), capacity))
+    }
+
+    #[cfg(any(all(feature = "dbus", target_os = "linux"), feature = "metrics"))]
+    fn publish_snapshot(&mut self) {
+        let snapshot = self.snapshot();
+
+        #[cfg(all(feature = "dbus", target_os = "linux"))]
+        if let Some(dbus) = &self.dbus {
+            let result = dbus.publish(&snapshot);
+            self.track_error("dbus", result);
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(server) = &self.metrics_server {
+            server.publish(&snapshot);
+        }
+    }
+
+    #[cfg(not(any(all(feature = "dbus", target_os = "linux"), feature = "metrics")))]
+    fn publish_snapshot(&mut self) {}
+
+    // Remet à zéro tout ce qui s'accumule depuis le lancement : historiques,
+    // pics, références apprises et instantané marqué
+    fn reset_all(&mut self) {
+        #[cfg(not(feature = "minimal"))]
+        {
+            self.charts.clear();
+            self.cpu_baseline.clear();
+        }
+        self.marked_snapshot = None;
+        self.comparison = None;
+
+        #[cfg(feature = "network")]
+        {
+            self.peak_down_mbps = 0.0;
+            self.peak_up_mbps = 0.0;
+            self.restart_rate_check();
+        }
+        #[cfg(all(feature = "network", not(feature = "minimal")))]
+        self.down_baseline.clear();
+    }
+}
+
+// Historiques des graphiques et géométrie de leurs courbes. Le profil `minimal`
+// ne compile ni ne conserve rien de tout cela.
+#[cfg(not(feature = "minimal"))]
+struct Charts {
+    cpu: History,
+    ram: History,
+    #[cfg(feature = "network")]
+    down: History,
+    #[cfg(feature = "network")]
+    up: History,
+    // Utilisation du lien en %, si `net_max_mbps` est configuré
+    #[cfg(feature = "network")]
+    utilization: History,
+    // Débits toutes interfaces confondues, sans courbe : le titre de la carte est
+    // lissé comme les graphiques, qui peuvent ne suivre qu'une interface
+    #[cfg(feature = "network")]
+    total_down: History,
+    #[cfg(feature = "network")]
+    total_up: History,
+    // Courbe de chaque interface de `State::interface_rates`
+    #[cfg(feature = "network")]
+    interfaces: BTreeMap<String, InterfaceChart>,
+    #[cfg(feature = "battery")]
+    battery: History,
+    #[cfg(feature = "disk")]
+    disk: History,
+    #[cfg(feature = "disk")]
+    disk_io: History,
+    // Graphiques choisis par la clé `charts`, dans l'ordre de la configuration
+    custom: Vec<(&'static MetricDef, History)>,
+    // Paire de la clé `correlation`, si ses deux métriques sont connues
+    correlation: Option<[(&'static MetricDef, History); 2]>,
+    // Mesures en attente du prochain point de chaque graphique (voir `push_samples`)
+    pending_points: Vec<PointBuffer>,
+    caches: ChartCaches,
+}
+
+#[cfg(not(feature = "minimal"))]
+impl Charts {
+    fn new(config: &Config) -> Self {
+        let len = config.history_len;
+
+        let custom: Vec<_> = config
+            .charts
+            .iter()
+            .filter_map(|name| {
+                let metric = find_metric(name);
+                if metric.is_none() {
+                    eprintln!("Graphique inconnu ignoré : {}", name);
+                }
+                metric
+            })
+            .map(|metric| (metric, History::new(len)))
+            .collect();
+
+        let correlation = config.correlation.as_ref().and_then(|(first, second)| {
+            match (find_metric(first), find_metric(second)) {
+                (Some(first), Some(second)) => {
+                    Some([(first, History::new(len)), (second, History::new(len))])
+                }
+                _ => {
+                    eprintln!("Corrélation ignorée, métrique inconnue : {} / {}", first, second);
+                    None
                 }
             }
         });
 
-        frame.stroke(&path, Stroke::default().with_width(2.0).with_color(self.color));
+        Self {
+            cpu: History::new(len),
+            ram: History::new(len),
+            #[cfg(feature = "network")]
+            down: History::new(len),
+            #[cfg(feature = "network")]
+            up: History::new(len),
+            #[cfg(feature = "network")]
+            utilization: History::new(len),
+            #[cfg(feature = "network")]
+            total_down: History::new(len),
+            #[cfg(feature = "network")]
+            total_up: History::new(len),
+            #[cfg(feature = "network")]
+            interfaces: BTreeMap::new(),
+            #[cfg(feature = "battery")]
+            battery: History::new(len),
+            #[cfg(feature = "disk")]
+            disk: History::new(len),
+            #[cfg(feature = "disk")]
+            disk_io: History::new(len),
+            caches: ChartCaches {
+                custom: custom.iter().map(|_| Cache::new()).collect(),
+                ..ChartCaches::default()
+            },
+            custom,
+            correlation,
+            pending_points: Vec::new(),
+        }
+    }
 
-        vec![frame.into_geometry()]
+    // Historiques nommés, dans l'ordre des colonnes du CSV
+    fn histories(&self) -> Vec<(&'static str, &History)> {
+        vec![
+            ("cpu", &self.cpu),
+            ("ram", &self.ram),
+            #[cfg(feature = "network")]
+            ("down_mbps", &self.down),
+            #[cfg(feature = "network")]
+            ("up_mbps", &self.up),
+            #[cfg(feature = "network")]
+            ("net_utilization", &self.utilization),
+            #[cfg(feature = "battery")]
+            ("battery", &self.battery),
+            #[cfg(feature = "disk")]
+            ("disk", &self.disk),
+            #[cfg(feature = "disk")]
+            ("disk_io_mb_s", &self.disk_io),
+        ]
+    }
+
+    fn histories_mut(&mut self) -> Vec<(&'static str, &mut History)> {
+        vec![
+            ("cpu", &mut self.cpu),
+            ("ram", &mut self.ram),
+            #[cfg(feature = "network")]
+            ("down_mbps", &mut self.down),
+            #[cfg(feature = "network")]
+            ("up_mbps", &mut self.up),
+            #[cfg(feature = "network")]
+            ("net_utilization", &mut self.utilization),
+            #[cfg(feature = "battery")]
+            ("battery", &mut self.battery),
+            #[cfg(feature = "disk")]
+            ("disk", &mut self.disk),
+            #[cfg(feature = "disk")]
+            ("disk_io_mb_s", &mut self.disk_io),
+        ]
+    }
+
+    // Historique d'une valeur affichée : ceux du CSV, plus les débits totaux
+    fn series(&self, name: &str) -> Option<&History> {
+        match name {
+            #[cfg(feature = "network")]
+            "down_total_mbps" => Some(&self.total_down),
+            #[cfg(feature = "network")]
+            "up_total_mbps" => Some(&self.total_up),
+            _ => self
+                .histories()
+                .into_iter()
+                .find(|(series, _)| *series == name)
+                .map(|(_, history)| history),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.caches.clear();
+        for buffer in &mut self.pending_points {
+            buffer.clear();
+        }
+        for (_, history) in self.histories_mut() {
+            history.clear();
+        }
+        #[cfg(feature = "network")]
+        {
+            self.total_down.clear();
+            self.total_up.clear();
+            self.interfaces.clear();
+        }
+        for (_, history) in self.custom.iter_mut().chain(self.correlation.iter_mut().flatten()) {
+            history.clear();
+        }
+    }
+
+    fn resize(&mut self, len: usize) {
+        self.caches.clear();
+        for (_, history) in self.histories_mut() {
+            history.set_capacity(len);
+        }
+        #[cfg(feature = "network")]
+        {
+            self.total_down.set_capacity(len);
+            self.total_up.set_capacity(len);
+            for chart in self.interfaces.values_mut() {
+                chart.history.set_capacity(len);
+                chart.cache.clear();
+            }
+        }
+        for (_, history) in self.custom.iter_mut().chain(self.correlation.iter_mut().flatten()) {
+            history.set_capacity(len);
+        }
+    }
+}
+
+#[cfg(all(feature = "network", not(feature = "minimal")))]
+struct InterfaceChart {
+    pending: PointBuffer,
+    history: History,
+    cache: Cache,
+}
+
+// Géométrie des graphiques mise en cache : elle n'est recalculée que lorsqu'un
+// nouvel échantillon arrive (ou que la taille change), pas à chaque rendu.
+#[cfg(not(feature = "minimal"))]
+#[derive(Default)]
+struct ChartCaches {
+    cpu: Cache,
+    ram: Cache,
+    #[cfg(feature = "network")]
+    down: Cache,
+    #[cfg(feature = "network")]
+    up: Cache,
+    #[cfg(feature = "network")]
+    utilization: Cache,
+    #[cfg(feature = "battery")]
+    battery: Cache,
+    #[cfg(feature = "disk")]
+    disk: Cache,
+    #[cfg(feature = "disk")]
+    disk_io: Cache,
+    // Un cache par graphique de `Charts::custom`
+    custom: Vec<Cache>,
+    correlation: Cache,
+}
+
+#[cfg(not(feature = "minimal"))]
+impl ChartCaches {
+    fn clear(&self) {
+        self.cpu.clear();
+        self.ram.clear();
+        #[cfg(feature = "network")]
+        {
+            self.down.clear();
+            self.up.clear();
+            self.utilization.clear();
+        }
+        #[cfg(feature = "battery")]
+        self.battery.clear();
+        #[cfg(feature = "disk")]
+        {
+            self.disk.clear();
+            self.disk_io.clear();
+        }
+        for cache in &self.custom {
+            cache.clear();
+        }
+        self.correlation.clear();
     }
 }